@@ -0,0 +1,196 @@
+//! Heap allocation tracking, to find the stages that churn through memory. When built with the
+//! `allocations` feature, a counting global allocator keeps per thread tallies on top of the
+//! system allocator; otherwise nothing is counted and measurements come back empty.
+
+use crate::metrics::{Metric, Metrics};
+use std::fmt::{Display, Formatter};
+
+/// Whether allocations are counted in this build
+pub const ENABLED: bool = cfg!(feature = "allocations");
+
+/// Heap usage of a measured closure
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Allocations {
+    /// Number of allocations, counting reallocations as well
+    pub count: u64,
+    /// Total size of all allocations, including the new size of each reallocation
+    pub bytes: u64,
+    /// Most bytes that were allocated at once on top of what was allocated before
+    pub peak: u64,
+}
+
+impl Allocations {
+    pub fn metrics(&self) -> Metrics {
+        vec![
+            ("allocations", Metric::Count(self.count)),
+            ("allocated", Metric::Bytes(self.bytes)),
+            ("peak live", Metric::Bytes(self.peak)),
+        ]
+    }
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations of {}, peak {}",
+            self.count,
+            Metric::Bytes(self.bytes),
+            Metric::Bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(feature = "allocations")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    #[derive(Debug, Default, Copy, Clone)]
+    pub struct Counters {
+        pub count: u64,
+        pub bytes: u64,
+        /// Allocated minus freed bytes, which goes negative on threads freeing what others
+        /// allocated
+        pub live: i64,
+        pub peak: i64,
+    }
+
+    thread_local! {
+        // Const initialized without a destructor, so that it can't allocate or be torn down
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters { count: 0, bytes: 0, live: 0, peak: 0 })
+        };
+    }
+
+    pub fn get() -> Counters {
+        COUNTERS.try_with(Cell::get).unwrap_or_default()
+    }
+
+    pub fn set(counters: Counters) {
+        let _ = COUNTERS.try_with(|cell| cell.set(counters));
+    }
+
+    fn allocated(size: usize) {
+        let mut counters = get();
+        counters.count += 1;
+        counters.bytes += size as u64;
+        counters.live += size as i64;
+        counters.peak = counters.peak.max(counters.live);
+        set(counters);
+    }
+
+    fn freed(size: usize) {
+        let mut counters = get();
+        counters.live -= size as i64;
+        set(counters);
+    }
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+}
+
+/// Runs the closure, counting the allocations it makes on the current thread
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Allocations) {
+    #[cfg(feature = "allocations")]
+    {
+        let before = counting::get();
+        counting::set(counting::Counters {
+            peak: before.live,
+            ..before
+        });
+
+        let result = f();
+
+        // Restore the peak of an enclosing measurement, if it was higher
+        let after = counting::get();
+        counting::set(counting::Counters {
+            peak: after.peak.max(before.peak),
+            ..after
+        });
+
+        let allocations = Allocations {
+            count: after.count - before.count,
+            bytes: after.bytes - before.bytes,
+            peak: (after.peak - before.live).max(0) as u64,
+        };
+        (result, allocations)
+    }
+    #[cfg(not(feature = "allocations"))]
+    (f(), Allocations::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_measure() {
+        let (sum, allocations) = measure(|| {
+            let numbers: Vec<u64> = black_box((0..1000).collect());
+            let (_, inner) = measure(|| black_box(vec![0u8; 100]));
+            assert_eq!(ENABLED, inner.count == 1 && inner.peak == 100);
+
+            numbers.iter().sum::<u64>()
+        });
+
+        assert_eq!(499500, sum);
+        match ENABLED {
+            true => {
+                assert!(allocations.count >= 2);
+                assert!(allocations.bytes >= 8100);
+                assert!(allocations.peak >= 8000);
+            }
+            false => assert_eq!(Allocations::default(), allocations),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let allocations = Allocations {
+            count: 3,
+            bytes: 1536,
+            peak: 512,
+        };
+
+        assert_eq!(
+            "3 allocations of 1.50 KiB, peak 512 B",
+            allocations.to_string()
+        );
+    }
+}
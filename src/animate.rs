@@ -0,0 +1,351 @@
+//! Frame by frame rendering of the simulation days, either in place in the terminal or headless
+//! into a text file.
+
+use crate::days;
+use crate::grid::Grid;
+use crate::runner::{self, Input};
+use crate::solution::Solution;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Read, Write};
+use std::process::{Command, ExitCode, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// Number of past frames kept around for stepping and seeking backwards
+const HISTORY: usize = 1000;
+/// Delay between checks for key presses while paused
+const PAUSED_POLL: Duration = Duration::from_millis(50);
+/// Byte read for Ctrl-C while the terminal doesn't turn it into an interrupt
+const CTRL_C: u8 = 0x03;
+
+pub const KEYS: &str =
+    "space pause, . step, , step back, ] skip 10, [ back 10, + faster, - slower, q or Ctrl-C quit";
+
+/// Snapshot of a simulation along with a line describing it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Frame {
+    pub grid: Grid<char>,
+    pub status: String,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.grid, self.status)
+    }
+}
+
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+/// Days whose simulation can be shown step by step
+pub trait Animate: Solution {
+    /// Lazily computed frames, as simulations can run for many thousands of steps
+    fn frames(self) -> Frames;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub fps: f64,
+    /// Writes all frames to this file instead of playing them in the terminal
+    pub output: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            fps: 10.0,
+            output: None,
+        }
+    }
+}
+
+/// State of the terminal player, changed by key presses
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playback {
+    pub position: usize,
+    pub paused: bool,
+    pub fps: f64,
+    pub quit: bool,
+}
+
+impl Playback {
+    pub fn new(fps: f64) -> Self {
+        Self {
+            position: 0,
+            paused: false,
+            fps,
+            quit: false,
+        }
+    }
+
+    pub fn handle(&mut self, key: u8) {
+        match key {
+            b' ' => self.paused = !self.paused,
+            b'.' => {
+                self.paused = true;
+                self.position += 1;
+            }
+            b',' => {
+                self.paused = true;
+                self.position = self.position.saturating_sub(1);
+            }
+            b']' => self.position += 10,
+            b'[' => self.position = self.position.saturating_sub(10),
+            b'+' => self.fps = (self.fps * 2.0).min(1000.0),
+            b'-' => self.fps = (self.fps / 2.0).max(0.5),
+            b'q' | CTRL_C => self.quit = true,
+            _ => {}
+        }
+    }
+}
+
+/// Frames read so far, of which only the most recent ones are kept
+struct History {
+    frames: Frames,
+    kept: VecDeque<Frame>,
+    /// Index of the first kept frame
+    first: usize,
+    finished: bool,
+}
+
+impl History {
+    fn new(frames: Frames) -> Self {
+        Self {
+            frames,
+            kept: VecDeque::new(),
+            first: 0,
+            finished: false,
+        }
+    }
+
+    /// Reads ahead up to the given frame, returning the closest available one along with its index
+    fn seek(&mut self, index: usize) -> Option<(usize, &Frame)> {
+        while !self.finished && self.first + self.kept.len() <= index {
+            match self.frames.next() {
+                Some(frame) => self.kept.push_back(frame),
+                None => self.finished = true,
+            }
+            if self.kept.len() > HISTORY {
+                self.kept.pop_front();
+                self.first += 1;
+            }
+        }
+
+        let last = (self.first + self.kept.len()).checked_sub(1)?;
+        let index = index.clamp(self.first, last);
+
+        Some((index, &self.kept[index - self.first]))
+    }
+}
+
+/// Puts the terminal in non-canonical mode so that single key presses can be read without
+/// blocking, and hides the cursor, restoring both when dropped. Ctrl-C is read as a key rather
+/// than interrupting the process, which would exit without restoring the terminal.
+struct RawTerminal {
+    settings: String,
+}
+
+impl RawTerminal {
+    fn enable() -> Option<Self> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+
+        let settings = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "0"])?;
+        print!("\x1b[?25l");
+
+        Some(Self { settings })
+    }
+
+    fn keys(&self) -> Vec<u8> {
+        let mut buffer = [0; 16];
+
+        match io::stdin().read(&mut buffer) {
+            Ok(count) => buffer[..count].to_vec(),
+            Err(_) => vec![],
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h");
+        let _ = io::stdout().flush();
+        stty(&[&self.settings]);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Plays the frames in place in the terminal. Keys only work when stdin is a terminal, in which
+/// case the cursor is hidden while playing.
+pub fn play(frames: Frames, fps: f64) -> io::Result<()> {
+    let terminal = RawTerminal::enable();
+    let mut history = History::new(frames);
+    let mut playback = Playback::new(fps);
+    let mut stdout = io::stdout().lock();
+
+    write!(stdout, "\x1b[2J")?;
+
+    while let Some((position, frame)) = history.seek(playback.position) {
+        playback.position = position;
+
+        // Draw over the previous frame from the top left, clearing whatever it left below
+        write!(
+            stdout,
+            "\x1b[H{}\x1b[J\nFrame {}{} at {} fps",
+            frame,
+            position,
+            if playback.paused { " (paused)" } else { "" },
+            playback.fps,
+        )?;
+        if terminal.is_some() {
+            write!(stdout, "\n{}", KEYS)?;
+        }
+        stdout.flush()?;
+
+        match playback.paused {
+            true => thread::sleep(PAUSED_POLL),
+            false => thread::sleep(Duration::from_secs_f64(1.0 / playback.fps)),
+        }
+
+        let before = playback.position;
+        for key in terminal.as_ref().map(RawTerminal::keys).unwrap_or_default() {
+            playback.handle(key);
+        }
+
+        if playback.quit {
+            break;
+        }
+        if !playback.paused && playback.position == before {
+            // Seeking past the end returns the last frame again
+            if history
+                .seek(position + 1)
+                .is_some_and(|(next, _)| next == position)
+            {
+                break;
+            }
+            playback.position += 1;
+        }
+    }
+
+    writeln!(stdout)
+}
+
+/// Writes all frames one after another, returning the number of frames written
+pub fn write(frames: Frames, output: &mut impl Write) -> io::Result<usize> {
+    let mut count = 0;
+
+    for (index, frame) in frames.enumerate() {
+        writeln!(output, "Frame {}: {}\n{}", index, frame.status, frame.grid)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Animates the given day, in the terminal or into the output file of the options
+pub fn run(number: u8, input: &Input, options: &Options) -> ExitCode {
+    let Some((day, animate)) = days::get(number).and_then(|day| Some((day, day.animate?))) else {
+        eprintln!("error: Day {} has no animation", number);
+        return ExitCode::FAILURE;
+    };
+    let frames = match runner::load_with(&day, input, animate) {
+        Ok(frames) => frames,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match &options.output {
+        Some(path) => File::create(path)
+            .and_then(|file| write(frames, &mut BufWriter::new(file)))
+            .map(|count| println!("Wrote {} frames to {}", count, path))
+            .map_err(|error| format!("Failed to write {}: {}", path, error)),
+        None => play(frames, options.fps).map_err(|error| error.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(count: usize) -> Frames {
+        Box::new((0..count).map(|i| Frame {
+            grid: Grid::new(2, 1, vec!['.', if i % 2 == 0 { '#' } else { '.' }]),
+            status: format!("Step {}", i),
+        }))
+    }
+
+    #[test]
+    fn test_playback_keys() {
+        let mut playback = Playback::new(10.0);
+
+        playback.handle(b']');
+        playback.handle(b'.');
+        assert_eq!((11, true), (playback.position, playback.paused));
+        playback.handle(b'[');
+        playback.handle(b'[');
+        playback.handle(b',');
+        assert_eq!(0, playback.position);
+        playback.handle(b' ');
+        playback.handle(b'+');
+        assert_eq!((false, 20.0), (playback.paused, playback.fps));
+        playback.handle(b'q');
+        assert!(playback.quit);
+
+        let mut playback = Playback::new(10.0);
+        playback.handle(CTRL_C);
+        assert!(playback.quit);
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = History::new(frames(HISTORY + 5));
+
+        assert_eq!(Some(3), history.seek(3).map(|(index, _)| index));
+        assert_eq!("Step 3", history.seek(3).unwrap().1.status);
+
+        // Seeking past the end stops at the last frame, after which the first ones are dropped
+        assert_eq!(
+            Some(HISTORY + 4),
+            history.seek(5000).map(|(index, _)| index)
+        );
+        assert_eq!(Some(5), history.seek(0).map(|(index, _)| index));
+
+        assert!(History::new(frames(0)).seek(0).is_none());
+    }
+
+    #[test]
+    fn test_write() {
+        let mut output = vec![];
+
+        assert_eq!(2, write(frames(2), &mut output).unwrap());
+        assert_eq!(
+            "Frame 0: Step 0\n.#\n\nFrame 1: Step 1\n..\n\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
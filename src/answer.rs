@@ -0,0 +1,206 @@
+use crate::json::Value;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+
+/// Largest integer that JSON numbers, being doubles, can hold exactly
+const MAX_EXACT_JSON: u128 = 1 << 53;
+
+/// Answer to one part of a puzzle, of any of the types the days return. Integers compare by
+/// value, whether they are signed or not.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    /// Answers that aren't numbers, like comma separated lists or letters read from a picture
+    Text(String),
+    /// An answer along with a drawing of how it was found, like day 14's Christmas tree
+    Rendered {
+        answer: Box<Answer>,
+        rendering: String,
+    },
+}
+
+impl Answer {
+    pub fn rendered(answer: impl Into<Answer>, rendering: impl Display) -> Self {
+        Answer::Rendered {
+            answer: Box::new(answer.into()),
+            rendering: rendering.to_string(),
+        }
+    }
+
+    /// The drawing attached to the answer, if any
+    pub fn rendering(&self) -> Option<&str> {
+        match self {
+            Answer::Rendered { rendering, .. } => Some(rendering),
+            _ => None,
+        }
+    }
+
+    /// Numbers too large to be represented exactly as JSON numbers are written as strings
+    pub fn to_json(&self) -> Value {
+        match self {
+            Answer::Unsigned(number) if *number <= MAX_EXACT_JSON => Value::Number(*number as f64),
+            Answer::Signed(number) if number.unsigned_abs() <= MAX_EXACT_JSON => {
+                Value::Number(*number as f64)
+            }
+            Answer::Unsigned(_) | Answer::Signed(_) | Answer::Text(_) => {
+                Value::String(self.to_string())
+            }
+            Answer::Rendered { answer, rendering } => Value::Object(vec![
+                ("answer".to_string(), answer.to_json()),
+                ("rendering".to_string(), Value::String(rendering.clone())),
+            ]),
+        }
+    }
+
+    /// Reads an answer written by [`Answer::to_json`]. Numbers written as strings come back as
+    /// text, which still compares equal to them as stored answers.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(number) if number.fract() == 0.0 && *number >= 0.0 => {
+                Some(Answer::Unsigned(*number as u128))
+            }
+            Value::Number(number) if number.fract() == 0.0 => Some(Answer::Signed(*number as i128)),
+            Value::String(string) => Some(Answer::Text(string.clone())),
+            Value::Object(_) => Some(Answer::Rendered {
+                answer: Box::new(Self::from_json(value.get("answer")?)?),
+                rendering: value.get("rendering")?.as_str()?.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    /// Formats the answer like it is submitted, leaving out any rendering
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Signed(number) => write!(f, "{}", number),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Rendered { answer, .. } => write!(f, "{}", answer),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Signed(b)) | (Answer::Signed(b), Answer::Unsigned(a)) => {
+                u128::try_from(*b).is_ok_and(|b| *a == b)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (
+                Answer::Rendered { answer, rendering },
+                Answer::Rendered {
+                    answer: other_answer,
+                    rendering: other_rendering,
+                },
+            ) => answer == other_answer && rendering == other_rendering,
+            _ => false,
+        }
+    }
+}
+
+/// Compares with an answer stored as text, like the accepted ones in the answers file
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Answer::Unsigned(number) => other == number.to_string(),
+            Answer::Signed(number) => other == number.to_string(),
+            Answer::Text(text) => other == text,
+            Answer::Rendered { answer, .. } => **answer == *other,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+macro_rules! from_integers {
+    ($variant:ident, $wide:ty, $($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(number: $type) -> Self {
+                    Answer::$variant(number as $wide)
+                }
+            }
+        )*
+    };
+}
+
+from_integers!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+from_integers!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// For parts that aren't solved yet, which never return an answer
+impl From<Infallible> for Answer {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_and_eq() {
+        assert_eq!("1206", Answer::from(1206u32).to_string());
+        assert_eq!("-3", Answer::from(-3i64).to_string());
+        assert_eq!(Answer::from(u128::MAX), *u128::MAX.to_string());
+        assert_eq!(Answer::from(42usize), "42");
+        assert_ne!(Answer::from(42usize), "42 ");
+        assert_eq!(Answer::rendered(7, "#.#\n"), "7");
+        assert_eq!(Some("#.#\n"), Answer::rendered(7, "#.#\n").rendering());
+        assert_eq!(Answer::from("1,2,3"), "1,2,3");
+
+        assert_eq!(Answer::from(3i64), Answer::from(3u8));
+        assert_eq!(Answer::from(3u8), Answer::from(3i64));
+        assert_ne!(Answer::from(-3i64), Answer::from(3u8));
+        assert_ne!(Answer::from(3u8), Answer::from("3"));
+        assert_ne!(Answer::rendered(7, "#"), Answer::from(7));
+        assert_ne!(Answer::rendered(7, "#"), Answer::rendered(7, "."));
+    }
+
+    #[test]
+    fn test_json() {
+        let answers = [
+            Answer::from(1206u32),
+            Answer::from(-3i64),
+            Answer::from(3i64),
+            Answer::from("1,2,3"),
+            Answer::rendered(7u32, "#.#\n"),
+        ];
+        for answer in answers {
+            assert_eq!(Some(answer.clone()), Answer::from_json(&answer.to_json()));
+        }
+
+        let large = Answer::from(u64::MAX);
+        assert_eq!(Value::String(u64::MAX.to_string()), large.to_json());
+        assert_eq!(
+            large,
+            *Answer::from_json(&large.to_json()).unwrap().to_string()
+        );
+        assert_eq!(
+            "{\"answer\":7,\"rendering\":\"#.#\\n\"}",
+            Answer::rendered(7u32, "#.#\n").to_json().to_string()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::parse::{self, ParseError};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::str::FromStr;
+
+pub const PATH: &str = "answers.txt";
+
+/// Response of the puzzle website to a submitted answer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Accepted,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "accepted" => Ok(Verdict::Accepted),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Accepted => "accepted",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+        })
+    }
+}
+
+/// A single submission of an answer to one part of a puzzle
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Submission history of all puzzles, one attempt per line in order of submission:
+/// `<day> <part> <verdict> <answer>`. Blank lines and lines starting with `#` are ignored, apart
+/// from `# Day <N>` lines.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Answers {
+    pub attempts: Vec<Attempt>,
+    /// Days that were started without any attempts yet, kept as `# Day <N>` lines until their
+    /// first attempt
+    pub started: Vec<u8>,
+}
+
+impl Answers {
+    pub fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            started: parse::lines(string)
+                .filter_map(|line| line.text.strip_prefix("# Day ")?.trim().parse().ok())
+                .collect(),
+            attempts: parse::lines(string)
+                .filter(|line| !line.text.trim().is_empty() && !line.text.starts_with('#'))
+                .map(|line| {
+                    let mut fields = line.fields();
+
+                    Ok(Attempt {
+                        day: fields.parse("a day")?,
+                        part: fields.parse("a part")?,
+                        verdict: fields
+                            .parse("a verdict 'accepted', 'too-high', 'too-low' or 'wrong'")?,
+                        answer: fields.parse("an answer")?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Loads the answers file, which is considered empty when it does not exist yet
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(PATH) {
+            Ok(string) => Self::from_string(&string)
+                .map_err(|error| format!("Failed to parse {}: {}", PATH, error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Failed to read {}: {}", PATH, error)),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(PATH, self.to_string())
+            .map_err(|error| format!("Failed to write {}: {}", PATH, error))
+    }
+
+    pub fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|attempt| attempt.verdict == Verdict::Accepted)
+            .map(|attempt| attempt.answer.as_str())
+    }
+
+    /// Verdict of an earlier submission of the same answer, if any
+    pub fn verdict_of(&self, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+        self.for_part(day, part)
+            .find(|attempt| attempt.answer == answer)
+            .map(|attempt| attempt.verdict)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "# <day> <part> <verdict> <answer>, in order of submission"
+        )?;
+
+        self.attempts.iter().try_for_each(|attempt| {
+            writeln!(
+                f,
+                "{} {} {} {}",
+                attempt.day, attempt.part, attempt.verdict, attempt.answer
+            )
+        })?;
+
+        self.started
+            .iter()
+            .filter(|&&day| self.attempts.iter().all(|attempt| attempt.day != day))
+            .try_for_each(|day| writeln!(f, "# Day {}", day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# comment\n\
+                          8 1 too-high 304\n\
+                          8 1 accepted 280\n\
+                          \n\
+                          8 2 accepted 958\n";
+
+    #[test]
+    fn test_lookup() {
+        let answers = Answers::from_string(SAMPLE).unwrap();
+
+        assert_eq!(3, answers.attempts.len());
+        assert_eq!(Some("280"), answers.accepted(8, 1));
+        assert_eq!(Some("958"), answers.accepted(8, 2));
+        assert_eq!(None, answers.accepted(9, 1));
+        assert_eq!(Some(Verdict::TooHigh), answers.verdict_of(8, 1, "304"));
+        assert_eq!(None, answers.verdict_of(8, 2, "304"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let answers = Answers::from_string(SAMPLE).unwrap();
+
+        assert_eq!(answers, Answers::from_string(&answers.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_started() {
+        let mut answers = Answers::from_string(&format!("{}# Day 17\n", SAMPLE)).unwrap();
+
+        assert_eq!(vec![17], answers.started);
+        assert!(answers
+            .to_string()
+            .ends_with("8 2 accepted 958\n# Day 17\n"));
+
+        // Entries of started days make way for their first attempt
+        answers.attempts.push(Attempt {
+            day: 17,
+            part: 1,
+            verdict: Verdict::Wrong,
+            answer: "4,6,3".to_string(),
+        });
+        assert!(answers
+            .to_string()
+            .ends_with("8 2 accepted 958\n17 1 wrong 4,6,3\n"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                5,
+                "expected a verdict 'accepted', 'too-high', 'too-low' or 'wrong', found 'maybe'"
+            )),
+            Answers::from_string("8 1 maybe 280")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                13,
+                "expected an answer, found end of line"
+            )),
+            Answers::from_string("8 1 accepted")
+        );
+    }
+}
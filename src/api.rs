@@ -0,0 +1,147 @@
+//! Stable entry point for other tools to solve puzzles with, without going through the command
+//! line or the registry
+
+use crate::answer::Answer;
+use crate::days;
+use crate::parse::ParseError;
+use crate::solution::{Param, Params};
+use std::fmt::{Display, Formatter};
+
+/// Settings that are not part of the puzzle input
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Options {
+    /// Day specific parameters, see [`Options::param`]
+    pub params: Params,
+}
+
+impl Options {
+    /// Sets a parameter of the days that have them, each only looking at its own:
+    ///
+    /// - day 4: `word`, the text searched for in part 1
+    /// - day 11: `blinks_1` and `blinks_2`, the number of blinks of each part, from 0 to 150
+    /// - day 13: `offset`, how much further away the prizes of part 2 are, `press_limit`, how
+    ///   often each button can be pressed in part 1, from 0 to `u64::MAX`, and `cost_a` and
+    ///   `cost_b` of the buttons, from 0 to `u32::MAX`
+    /// - day 14: `width` and `height` of the area, from 1 to `i32::MAX`, and the `seconds` of
+    ///   part 1, from 0 to `u32::MAX`
+    /// - day 16: `move_cost` and `turn_cost`, the points of moving forward and turning, from 0
+    ///   to `u32::MAX`
+    ///
+    /// Values out of range make solving fail with [`Error::Parse`].
+    pub fn param(mut self, key: &str, value: i64) -> Self {
+        self.params.set(key, value);
+        self
+    }
+
+    /// Sets a text parameter, see [`Options::param`]
+    pub fn text_param(mut self, key: &str, value: &str) -> Self {
+        self.params.insert(key, Param::Text(value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    UnknownDay(u8),
+    UnknownPart(u8),
+    Parse(ParseError),
+    /// The part has no solution yet
+    Unsolved {
+        day: u8,
+        part: u8,
+    },
+    /// The part is solved, but found no answer for the input
+    NoAnswer {
+        day: u8,
+        part: u8,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownDay(day) => write!(f, "Day {} is not solved", day),
+            Error::UnknownPart(part) => write!(f, "Unknown part {}, expected 1 or 2", part),
+            Error::Parse(error) => write!(f, "Failed to parse input: {}", error),
+            Error::Unsolved { day, part } => {
+                write!(f, "Part {} of day {} is not solved", part, day)
+            }
+            Error::NoAnswer { day, part } => {
+                write!(f, "Found no answer to part {} of day {}", part, day)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+/// Solves one part of the given day's puzzle for the input
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer, Error> {
+    solve_with(day, part, input, &Options::default())
+}
+
+/// Solves one part of the given day's puzzle for the input, using the given options
+pub fn solve_with(day: u8, part: u8, input: &str, options: &Options) -> Result<Answer, Error> {
+    let number = day;
+    let day = days::get(number).ok_or(Error::UnknownDay(number))?;
+    if !(1..=2).contains(&part) {
+        return Err(Error::UnknownPart(part));
+    }
+    let solver = (day.parse)(input, &options.params)?;
+
+    match part {
+        1 => Ok(solver.part_1()),
+        _ if part > day.parts => Err(Error::Unsolved { day: number, part }),
+        _ => solver.part_2().ok_or(Error::NoAnswer { day: number, part }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let sample = days::get(1).unwrap().sample;
+
+        assert_eq!(Ok(Answer::from(11u32)), solve(1, 1, sample));
+        assert_eq!(Ok(Answer::from(31u32)), solve(1, 2, sample));
+        assert_eq!(Err(Error::UnknownDay(26)), solve(26, 1, sample));
+        assert_eq!(Err(Error::UnknownPart(3)), solve(1, 3, sample));
+        assert!(matches!(solve(1, 1, "1 x"), Err(Error::Parse(_))));
+        assert_eq!(
+            Err(Error::Unsolved { day: 16, part: 2 }),
+            solve(16, 2, days::get(16).unwrap().sample)
+        );
+    }
+
+    #[test]
+    fn test_solve_with() {
+        let options = Options::default().param("width", 11).param("height", 7);
+
+        assert_eq!(
+            Ok(Answer::from(12usize)),
+            solve_with(14, 1, days::get(14).unwrap().sample, &options)
+        );
+        // The robots of the sample never form a Christmas tree
+        assert_eq!(
+            Err(Error::NoAnswer { day: 14, part: 2 }),
+            solve_with(14, 2, days::get(14).unwrap().sample, &options)
+        );
+        assert_eq!(
+            Ok(Answer::from(18usize)),
+            solve_with(
+                4,
+                1,
+                days::get(4).unwrap().sample,
+                &Options::default().text_param("word", "SAMX")
+            )
+        );
+    }
+}
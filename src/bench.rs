@@ -0,0 +1,408 @@
+use crate::allocations::{self, Allocations};
+use crate::json::Value;
+use crate::metrics::Metric;
+use crate::parse::ParseError;
+use crate::runner::{self, Selection, Source};
+use crate::solution::{Day, Params};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Baseline that every run updates with the days it measured, shown by `aoc status`
+pub const LAST: &str = "target/bench-last.json";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the given samples, which must not be empty
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+    /// Allocations of the last iteration, which are empty in builds that don't count them
+    pub allocations: Allocations,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub iterations: usize,
+    /// Path of a baseline to write the measurements to
+    pub save: Option<String>,
+    /// Path of a baseline to compare the measurements with
+    pub compare: Option<String>,
+    /// Relative slowdown of the median above which a stage counts as regressed
+    pub threshold: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            save: None,
+            compare: None,
+            threshold: 0.1,
+        }
+    }
+}
+
+/// Times parsing and both parts of a day, each repeated the given number of times
+pub fn measure(day: &Day, input: &str, iterations: usize) -> Result<Vec<Measurement>, ParseError> {
+    let mut samples = [vec![], vec![], vec![]];
+    let mut allocations = [Allocations::default(); 3];
+    let mut has_part_2 = true;
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let (solver, parsed) =
+            allocations::measure(|| (day.parse)(black_box(input), &Params::default()));
+        samples[0].push(start.elapsed());
+        let solver = solver?;
+        allocations[0] = parsed;
+
+        let start = Instant::now();
+        (_, allocations[1]) = allocations::measure(|| black_box(solver.part_1()));
+        samples[1].push(start.elapsed());
+
+        let start = Instant::now();
+        let part_2;
+        (part_2, allocations[2]) = allocations::measure(|| black_box(solver.part_2()));
+        has_part_2 = part_2.is_some();
+        samples[2].push(start.elapsed());
+    }
+
+    Ok(Stage::ALL
+        .into_iter()
+        .zip(samples)
+        .zip(allocations)
+        .filter(|((stage, _), _)| *stage != Stage::Part2 || has_part_2)
+        .map(|((stage, samples), allocations)| Measurement {
+            day: day.number,
+            stage,
+            stats: Stats::from_samples(samples),
+            allocations,
+        })
+        .collect())
+}
+
+/// Baseline of median durations in nanoseconds, keyed by day and stage
+pub fn to_baseline(measurements: &[Measurement]) -> Value {
+    let mut days: Vec<(String, Value)> = vec![];
+
+    for measurement in measurements {
+        let key = measurement.day.to_string();
+        if days.last().is_none_or(|(day, _)| *day != key) {
+            days.push((key, Value::Object(vec![])));
+        }
+
+        if let Some((_, Value::Object(stages))) = days.last_mut() {
+            stages.push((
+                measurement.stage.to_string(),
+                Value::Number(measurement.stats.median.as_nanos() as f64),
+            ));
+        }
+    }
+
+    Value::Object(days)
+}
+
+/// Replaces the days of the baseline that were measured, keeping the others in order of day
+pub fn merge_baseline(baseline: &Value, measurements: &[Measurement]) -> Value {
+    let measured = to_baseline(measurements);
+    let mut days: Vec<(String, Value)> = baseline
+        .entries()
+        .iter()
+        .filter(|(day, _)| measured.get(day).is_none())
+        .cloned()
+        .chain(measured.entries().iter().cloned())
+        .collect();
+    days.sort_by_key(|(day, _)| day.parse::<u8>().unwrap_or(u8::MAX));
+
+    Value::Object(days)
+}
+
+/// Total of the median durations of a day's stages in the baseline
+pub fn baseline_total(baseline: &Value, day: u8) -> Option<Duration> {
+    let stages = baseline.get(&day.to_string())?.entries();
+    let nanos: f64 = stages
+        .iter()
+        .filter_map(|(_, median)| median.as_f64())
+        .sum();
+
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+/// Reads a baseline written by [`run`]
+pub fn load_baseline(path: &str) -> Result<Value, String> {
+    let string =
+        fs::read_to_string(path).map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+    Value::parse(&string).map_err(|error| format!("Failed to parse {}: {}", path, error))
+}
+
+fn save_baseline(path: &str, baseline: &Value) -> Result<(), String> {
+    if let Some(parent) = Path::new(path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    fs::write(path, baseline.pretty() + "\n")
+        .map_err(|error| format!("Failed to write {}: {}", path, error))
+}
+
+/// Stages whose median got slower than the baseline by more than the threshold, along with the
+/// relative change
+pub fn regressions<'a>(
+    measurements: &'a [Measurement],
+    baseline: &Value,
+    threshold: f64,
+) -> Vec<(&'a Measurement, f64)> {
+    measurements
+        .iter()
+        .filter_map(|measurement| {
+            let before = baseline
+                .get(&measurement.day.to_string())?
+                .get(&measurement.stage.to_string())?
+                .as_f64()?;
+            let change = measurement.stats.median.as_nanos() as f64 / before.max(1.0) - 1.0;
+
+            (change > threshold).then_some((measurement, change))
+        })
+        .collect()
+}
+
+/// Benchmarks the selected days against their inputs, optionally saving or comparing a baseline
+pub fn run(selection: Selection, options: &Options) -> ExitCode {
+    let baseline = match options.compare.as_deref().map(load_baseline) {
+        Some(Ok(baseline)) => Some(baseline),
+        Some(Err(error)) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut measurements = vec![];
+
+    print!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "day", "stage", "min", "median", "max"
+    );
+    if allocations::ENABLED {
+        print!(
+            "  {:>11}  {:>12}  {:>12}",
+            "allocations", "allocated", "peak live"
+        );
+    }
+    println!();
+
+    for day in selection.days() {
+        let input = match Source::Default.read(&day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let day_measurements = match measure(&day, &input, options.iterations) {
+            Ok(measurements) => measurements,
+            Err(error) => {
+                let path = runner::input_path(day.number);
+                eprintln!("error: {}", runner::Error::Parse { path, input, error });
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        for measurement in &day_measurements {
+            let Measurement {
+                day,
+                stage,
+                stats,
+                allocations,
+            } = measurement;
+            print!(
+                "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+                day,
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max)
+            );
+            if allocations::ENABLED {
+                print!(
+                    "  {:>11}  {:>12}  {:>12}",
+                    allocations.count,
+                    Metric::Bytes(allocations.bytes).to_string(),
+                    Metric::Bytes(allocations.peak).to_string()
+                );
+            }
+            println!();
+        }
+
+        measurements.extend(day_measurements);
+    }
+
+    if let Some(path) = &options.save {
+        if let Err(error) = save_baseline(path, &to_baseline(&measurements)) {
+            eprintln!("error: {}", error);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    let last = load_baseline(LAST).unwrap_or(Value::Object(vec![]));
+    if let Err(error) = save_baseline(LAST, &merge_baseline(&last, &measurements)) {
+        eprintln!("warning: {}", error);
+    }
+
+    if let Some(baseline) = baseline {
+        let regressions = regressions(&measurements, &baseline, options.threshold);
+
+        for (measurement, change) in &regressions {
+            println!(
+                "Regression: day {} {} is {:.0}% slower than the baseline",
+                measurement.day,
+                measurement.stage,
+                change * 100.0
+            );
+        }
+
+        if !regressions.is_empty() {
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(day: u8, stage: Stage, median_nanos: u64) -> Measurement {
+        let median = Duration::from_nanos(median_nanos);
+
+        Measurement {
+            day,
+            stage,
+            stats: Stats {
+                min: median,
+                median,
+                max: median,
+            },
+            allocations: Allocations::default(),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 4, 2].map(Duration::from_millis).to_vec();
+
+        assert_eq!(
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            },
+            Stats::from_samples(samples)
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = crate::days::get(1).unwrap();
+        let measurements = measure(&day, day.sample, 2).unwrap();
+
+        assert_eq!(3, measurements.len());
+        assert_eq!(
+            allocations::ENABLED,
+            measurements[0].allocations.count > 0 && measurements[0].allocations.peak > 0
+        );
+    }
+
+    #[test]
+    fn test_merge_baseline() {
+        let before = to_baseline(&[
+            measurement(1, Stage::Parse, 100),
+            measurement(3, Stage::Parse, 300),
+        ]);
+        let merged = merge_baseline(
+            &before,
+            &[
+                measurement(2, Stage::Part1, 200),
+                measurement(3, Stage::Parse, 250),
+                measurement(3, Stage::Part1, 50),
+            ],
+        );
+
+        assert_eq!(
+            "{\"1\":{\"parse\":100},\"2\":{\"part1\":200},\"3\":{\"parse\":250,\"part1\":50}}",
+            merged.to_string()
+        );
+        assert_eq!(Some(Duration::from_nanos(300)), baseline_total(&merged, 3));
+        assert_eq!(None, baseline_total(&merged, 4));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let before = [
+            measurement(1, Stage::Parse, 100),
+            measurement(1, Stage::Part1, 1000),
+            measurement(2, Stage::Part2, 1000),
+        ];
+        let baseline = Value::parse(&to_baseline(&before).to_string()).unwrap();
+        let after = [
+            measurement(1, Stage::Parse, 105),
+            measurement(1, Stage::Part1, 1500),
+            measurement(2, Stage::Part2, 900),
+            measurement(3, Stage::Parse, 100),
+        ];
+
+        let regressions = regressions(&after, &baseline, 0.1);
+
+        assert_eq!(1, regressions.len());
+        assert_eq!(&after[1], regressions[0].0);
+        assert!((regressions[0].1 - 0.5).abs() < 1e-9);
+    }
+}
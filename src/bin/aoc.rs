@@ -0,0 +1,73 @@
+use aoc24::cli::{self, Command};
+use aoc24::runner::Selection;
+use aoc24::{animate, bench, cache, image, inputs, pool, runner, scaffold, status, submit, trace};
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut command = cli::parse(&args);
+
+    if let Ok(Command::Run { trace, .. }) = &command {
+        if let Err(error) = trace::init(trace) {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Ok(Command::Run { input, .. } | Command::Image { input, .. }) = &mut command {
+        if let Err(error) = input.load_config() {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match command {
+        Ok(Command::Run {
+            selection: Selection::Day(number),
+            input,
+            animate: Some(options),
+            ..
+        }) => animate::run(number, &input, &options),
+        Ok(Command::Run {
+            selection: Selection::All,
+            part,
+            input,
+            jobs,
+            stats,
+            cache,
+            ..
+        }) => runner::run_all(
+            part,
+            &input,
+            jobs.unwrap_or_else(pool::default_workers),
+            stats,
+            cache,
+        ),
+        Ok(Command::Run {
+            selection,
+            part,
+            input,
+            stats,
+            cache,
+            ..
+        }) => runner::run_selection(selection, part, &input, stats, cache),
+        Ok(Command::Verify { selection }) => runner::verify(selection),
+        Ok(Command::Fetch { selection }) => inputs::run(selection),
+        Ok(Command::Submit { day, part, answer }) => submit::run(day, part, answer),
+        Ok(Command::Bench { selection, options }) => bench::run(selection, &options),
+        Ok(Command::Image {
+            day,
+            path,
+            scale,
+            input,
+        }) => image::run(day, &path, scale, &input),
+        Ok(Command::New { day }) => scaffold::run(day),
+        Ok(Command::Status { markdown }) => status::run(markdown),
+        Ok(Command::ClearCache) => cache::clear(),
+        Err(message) => {
+            eprintln!("{}\n{}", message, cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Answers computed before, so that slow parts aren't solved again while nothing changed.
+//!
+//! Entries are keyed by a hash of the input and the parameters, and stored in a directory per
+//! version of the running binary, which is told by its size and modification time so that it is
+//! cheap to find. Rebuilding makes for a new version, and the directories of other versions are
+//! removed when the cache is opened, before any answers are looked up or stored.
+
+use crate::answer::Answer;
+use crate::json::Value;
+use crate::solution::Params;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::OnceLock;
+
+pub const DIRECTORY: &str = "target/answers";
+
+/// Identifies the answer to one part for one input
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Key {
+    pub day: u8,
+    pub part: u8,
+    pub hash: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    directory: PathBuf,
+    /// Hash of the size and modification time of the binary that computes the answers
+    version: u64,
+}
+
+impl Cache {
+    pub fn new(directory: impl Into<PathBuf>, version: u64) -> Self {
+        Self {
+            directory: directory.into(),
+            version,
+        }
+    }
+
+    /// Cache of the running binary, unless its metadata can't be read to tell its version. The
+    /// first call removes the entries of other versions.
+    pub fn open() -> Option<Self> {
+        static CACHE: OnceLock<Option<Cache>> = OnceLock::new();
+        CACHE
+            .get_or_init(|| {
+                let metadata = fs::metadata(env::current_exe().ok()?).ok()?;
+                let mut hasher = DefaultHasher::new();
+                (metadata.len(), metadata.modified().ok()?).hash(&mut hasher);
+
+                let cache = Self::new(DIRECTORY, hasher.finish());
+                if let Err(error) = cache.remove_stale() {
+                    eprintln!("warning: Failed to remove stale cached answers: {}", error);
+                }
+                Some(cache)
+            })
+            .clone()
+    }
+
+    pub fn key(&self, day: u8, part: u8, input: &str, params: &Params) -> Key {
+        let mut hasher = DefaultHasher::new();
+        (self.version, day, part, input, params).hash(&mut hasher);
+
+        Key {
+            day,
+            part,
+            hash: hasher.finish(),
+        }
+    }
+
+    fn version_directory(&self) -> PathBuf {
+        self.directory.join(format!("{:016x}", self.version))
+    }
+
+    fn path(&self, key: Key) -> PathBuf {
+        self.version_directory().join(format!(
+            "day{}-part{}-{:016x}.json",
+            key.day, key.part, key.hash
+        ))
+    }
+
+    /// Answer stored for the key, where unreadable entries count as missing
+    pub fn get(&self, key: Key) -> Option<Answer> {
+        let string = fs::read_to_string(self.path(key)).ok()?;
+
+        Answer::from_json(&Value::parse(&string).ok()?)
+    }
+
+    pub fn insert(&self, key: Key, answer: &Answer) -> io::Result<()> {
+        fs::create_dir_all(self.version_directory())?;
+        fs::write(self.path(key), answer.to_json().to_string())
+    }
+
+    /// Removes everything but the directory of this version
+    fn remove_stale(&self) -> io::Result<()> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        let current = self.version_directory();
+        for entry in entries {
+            let path = entry?.path();
+            if path == current {
+                continue;
+            } else if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            } else {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes all entries of all versions, returning how many there were
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = match count_files(&self.directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => return Err(error),
+        };
+        fs::remove_dir_all(&self.directory)?;
+
+        Ok(entries)
+    }
+}
+
+fn count_files(directory: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        count += match path.is_dir() {
+            true => count_files(&path)?,
+            false => 1,
+        };
+    }
+
+    Ok(count)
+}
+
+/// Removes all cached answers
+pub fn clear() -> ExitCode {
+    match Cache::new(DIRECTORY, 0).clear() {
+        Ok(count) => {
+            println!("Removed {} cached answers from {}", count, DIRECTORY);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: Failed to clear {}: {}", DIRECTORY, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let directory = env::temp_dir().join(format!("aoc24-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let cache = Cache::new(&directory, 1);
+        let params: Params = "width=11".parse().unwrap();
+        let key = cache.key(14, 2, "p=0,4 v=3,-3\n", &params);
+
+        assert_eq!(None, cache.get(key));
+        cache.insert(key, &Answer::rendered(7u32, "#.#\n")).unwrap();
+        assert_eq!(Some(Answer::rendered(7u32, "#.#\n")), cache.get(key));
+
+        // Anything that can change the answer makes for another key
+        let keys = [
+            cache.key(14, 1, "p=0,4 v=3,-3\n", &params),
+            cache.key(14, 2, "p=0,4 v=3,-2\n", &params),
+            cache.key(14, 2, "p=0,4 v=3,-3\n", &Params::default()),
+            Cache::new(&directory, 2).key(14, 2, "p=0,4 v=3,-3\n", &params),
+        ];
+        for other in keys {
+            assert_ne!(key, other);
+            assert_eq!(None, cache.get(other));
+        }
+
+        assert_eq!(1, cache.clear().unwrap());
+        assert_eq!(None, cache.get(key));
+        assert_eq!(0, cache.clear().unwrap());
+    }
+
+    #[test]
+    fn test_stale_versions() {
+        let directory = env::temp_dir().join(format!("aoc24-stale-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let old = Cache::new(&directory, 1);
+        let new = Cache::new(&directory, 2);
+        let params = Params::default();
+        let (old_key, new_key) = (old.key(1, 1, "1\n", &params), new.key(1, 1, "1\n", &params));
+
+        old.insert(old_key, &Answer::from(1u32)).unwrap();
+        old.insert(old.key(1, 2, "1\n", &params), &Answer::from(2u32))
+            .unwrap();
+        assert_eq!(Some(Answer::from(1u32)), old.get(old_key));
+
+        // Storing answers of the new version leaves the old ones until the cache is opened
+        new.insert(new_key, &Answer::from(3u32)).unwrap();
+        assert_eq!(Some(Answer::from(1u32)), old.get(old_key));
+        new.remove_stale().unwrap();
+        assert_eq!(None, old.get(old_key));
+        assert_eq!(Some(Answer::from(3u32)), new.get(new_key));
+        new.remove_stale().unwrap();
+        assert_eq!(Some(Answer::from(3u32)), new.get(new_key));
+        assert_eq!(1, new.clear().unwrap());
+
+        // Workers storing their first answers at once don't remove each other's
+        let newer = Cache::new(&directory, 3);
+        std::thread::scope(|scope| {
+            for part in 1..=8 {
+                let newer = &newer;
+                scope.spawn(move || {
+                    let key = newer.key(1, part, "1\n", &Params::default());
+                    newer.insert(key, &Answer::from(part)).unwrap();
+                });
+            }
+        });
+        assert_eq!(8, newer.clear().unwrap());
+    }
+}
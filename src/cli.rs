@@ -0,0 +1,684 @@
+use crate::runner::{Input, Selection, Source};
+use crate::{animate, bench, image, trace};
+use crate::{config, days};
+use std::str::FromStr;
+
+pub const USAGE: &str = "\
+Usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--sample] [--params <key=value,...>]
+               [--set <dayN.key=value>] [--animate] [--fps <n>] [--frames <path>] [--jobs <n>] [--stats]
+               [--no-cache] [--trace <module[=level],...>] [--trace-file <path>]
+       aoc verify [day|all]
+       aoc fetch <day|all>
+       aoc submit <day> <1|2> [answer]
+       aoc bench [day|all] [--iterations <n>] [--save <path>] [--compare <path>] [--threshold <percent>]
+       aoc image <day> <path.ppm|path.pgm|path.svg> [--scale <n>] [--input <path|->] [--sample]
+                 [--params <key=value,...>] [--set <dayN.key=value>]
+       aoc new <day>
+       aoc status [--markdown]
+       aoc cache clear";
+
+pub const DAY_USAGE: &str = "\
+Usage: dayN [path|-] [--sample] [--params <key=value,...>] [--set <dayN.key=value>] [--animate]
+            [--fps <n>] [--frames <path>]";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        input: Input,
+        /// Animates the simulation instead of printing the answers
+        animate: Option<animate::Options>,
+        /// Maximum number of worker threads when running all days
+        jobs: Option<usize>,
+        /// Prints the metrics and allocations recorded while solving each part
+        stats: bool,
+        /// Looks up answers computed before for the same input and code, and stores new ones
+        cache: bool,
+        trace: trace::Options,
+    },
+    Verify {
+        selection: Selection,
+    },
+    Fetch {
+        selection: Selection,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+    },
+    Bench {
+        selection: Selection,
+        options: bench::Options,
+    },
+    Image {
+        day: u8,
+        path: String,
+        /// Size in pixels of each grid cell
+        scale: usize,
+        input: Input,
+    },
+    /// Generates the files of a day that is yet to be solved
+    New {
+        day: u8,
+    },
+    Status {
+        /// Prints a Markdown table instead of the terminal one
+        markdown: bool,
+    },
+    ClearCache,
+}
+
+/// Parses the command line arguments, excluding the program name
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    match args.next() {
+        Some("run") => {
+            let selection = parse_selection(args.next())?;
+            let mut part = None;
+            let mut input = Input::default();
+            let mut animate = None;
+            let mut jobs = None;
+            let mut stats = false;
+            let mut cache = true;
+            let mut trace = trace::Options::default();
+
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--part" => part = Some(parse_part(args.next())?),
+                    "--stats" => stats = true,
+                    "--no-cache" => cache = false,
+                    "--trace" => {
+                        trace.filter = Some(args.next().ok_or("Missing trace filter")?.parse()?);
+                    }
+                    "--trace-file" => trace.file = Some(parse_path(args.next())?),
+                    "--jobs" => match parse_number(args.next(), "number of jobs")? {
+                        0 => return Err("Invalid number of jobs '0'".to_string()),
+                        n => jobs = Some(n),
+                    },
+                    "--input" => {
+                        input.source = Source::from_arg(args.next().ok_or("Missing input path")?);
+                    }
+                    "--animate" | "--fps" | "--frames" => {
+                        parse_animate_option(arg, &mut args, &mut animate)?
+                    }
+                    _ => parse_input_option(arg, &mut args, &mut input)?,
+                }
+            }
+
+            if selection == Selection::All
+                && matches!(input.source, Source::Path(_) | Source::Stdin)
+            {
+                return Err("An input path can only be given for a single day".to_string());
+            }
+            if selection == Selection::All && animate.is_some() {
+                return Err("Only a single day can be animated".to_string());
+            }
+            if selection != Selection::All && jobs.is_some() {
+                return Err("The number of jobs only applies to running all days".to_string());
+            }
+            let numbers: Vec<u8> = selection.days().iter().map(|day| day.number).collect();
+            config::check_params(&numbers, &input.params)?;
+
+            Ok(Command::Run {
+                selection,
+                part,
+                input,
+                animate,
+                jobs,
+                stats,
+                cache,
+                trace,
+            })
+        }
+        Some("verify") => {
+            let selection = match args.next() {
+                None => Selection::All,
+                arg => parse_selection(arg)?,
+            };
+
+            match args.next() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(Command::Verify { selection }),
+            }
+        }
+        Some("fetch") => {
+            let selection = parse_selection(args.next())?;
+
+            match args.next() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(Command::Fetch { selection }),
+            }
+        }
+        Some("submit") => {
+            let Selection::Day(day) = parse_selection(args.next())? else {
+                return Err("Answers can only be submitted for a single day".to_string());
+            };
+            let part = parse_part(args.next())?;
+            let answer = args.next().map(str::to_string);
+
+            match args.next() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(Command::Submit { day, part, answer }),
+            }
+        }
+        Some("bench") => {
+            let mut args = args.peekable();
+            let selection = match args.next_if(|arg| !arg.starts_with("--")) {
+                None => Selection::All,
+                arg => parse_selection(arg)?,
+            };
+            let mut options = bench::Options::default();
+
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--iterations" => {
+                        options.iterations = parse_number(args.next(), "iterations")?;
+                    }
+                    "--save" => options.save = Some(parse_path(args.next())?),
+                    "--compare" => options.compare = Some(parse_path(args.next())?),
+                    "--threshold" => {
+                        options.threshold = parse_number::<f64>(args.next(), "threshold")? / 100.0;
+                    }
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+
+            Ok(Command::Bench { selection, options })
+        }
+        Some("image") => {
+            let Selection::Day(day) = parse_selection(args.next())? else {
+                return Err("Images can only be made of a single day".to_string());
+            };
+            let path = parse_path(args.next())?;
+            if image::Format::of_path(&path).is_none() {
+                return Err(format!(
+                    "Unknown image format of '{}', expected .ppm, .pgm or .svg",
+                    path
+                ));
+            }
+            let mut scale = 1;
+            let mut input = Input::default();
+
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--scale" => match parse_number(args.next(), "scale")? {
+                        0 => return Err("Invalid scale '0'".to_string()),
+                        n => scale = n,
+                    },
+                    "--input" => {
+                        input.source = Source::from_arg(args.next().ok_or("Missing input path")?);
+                    }
+                    _ => parse_input_option(arg, &mut args, &mut input)?,
+                }
+            }
+            config::check_params(&[day], &input.params)?;
+
+            Ok(Command::Image {
+                day,
+                path,
+                scale,
+                input,
+            })
+        }
+        Some("new") => {
+            let day = match parse_number(args.next(), "day")? {
+                day @ 1..=25 => day,
+                day => return Err(format!("Invalid day '{}', expected 1 to 25", day)),
+            };
+
+            match args.next() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(Command::New { day }),
+            }
+        }
+        Some("status") => match args.next() {
+            None => Ok(Command::Status { markdown: false }),
+            Some("--markdown") => match args.next() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(Command::Status { markdown: true }),
+            },
+            Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        },
+        Some("cache") => match (args.next(), args.next()) {
+            (Some("clear"), None) => Ok(Command::ClearCache),
+            (Some("clear"), Some(arg)) => Err(format!("Unexpected argument '{}'", arg)),
+            (Some(action), _) => Err(format!("Unknown cache action '{}'", action)),
+            (None, _) => Err("Missing cache action".to_string()),
+        },
+        Some(command) => Err(format!("Unknown command '{}'", command)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+/// Parses the arguments of the binary of the given day, excluding the program name
+pub fn parse_day(day: u8, args: &[String]) -> Result<(Input, Option<animate::Options>), String> {
+    let mut args = args.iter().map(String::as_str);
+    let mut input = Input::default();
+    let mut animate = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "-" => input.source = Source::Stdin,
+            path if !path.starts_with("--") && input.source == Source::Default => {
+                input.source = Source::from_arg(path);
+            }
+            "--animate" | "--fps" | "--frames" => {
+                parse_animate_option(arg, &mut args, &mut animate)?
+            }
+            _ => parse_input_option(arg, &mut args, &mut input)?,
+        }
+    }
+    config::check_params(&[day], &input.params)?;
+
+    Ok((input, animate))
+}
+
+/// Parses the options shared by all commands that run days
+fn parse_input_option<'a>(
+    arg: &str,
+    args: &mut impl Iterator<Item = &'a str>,
+    input: &mut Input,
+) -> Result<(), String> {
+    match arg {
+        "--sample" => input.source = Source::Sample,
+        "--params" => input.params = args.next().ok_or("Missing parameters")?.parse()?,
+        "--set" => input
+            .config
+            .set_arg(args.next().ok_or("Missing setting")?)?,
+        _ => return Err(format!("Unexpected argument '{}'", arg)),
+    }
+
+    Ok(())
+}
+
+/// Parses the animation options, of which any one enables the animation
+fn parse_animate_option<'a>(
+    arg: &str,
+    args: &mut impl Iterator<Item = &'a str>,
+    animate: &mut Option<animate::Options>,
+) -> Result<(), String> {
+    let options = animate.get_or_insert_with(animate::Options::default);
+
+    match arg {
+        "--animate" => {}
+        "--fps" => match parse_number(args.next(), "frame rate")? {
+            fps if fps > 0.0 => options.fps = fps,
+            fps => return Err(format!("Invalid frame rate '{}'", fps)),
+        },
+        "--frames" => options.output = Some(parse_path(args.next())?),
+        _ => return Err(format!("Unexpected argument '{}'", arg)),
+    }
+
+    Ok(())
+}
+
+fn parse_selection(arg: Option<&str>) -> Result<Selection, String> {
+    match arg {
+        Some("all") => Ok(Selection::All),
+        Some(day) => match day.parse() {
+            Ok(number) if days::get(number).is_some() => Ok(Selection::Day(number)),
+            _ => Err(format!("Unknown day '{}'", day)),
+        },
+        None => Err("Missing day".to_string()),
+    }
+}
+
+fn parse_part(arg: Option<&str>) -> Result<u8, String> {
+    match arg {
+        Some("1") => Ok(1),
+        Some("2") => Ok(2),
+        Some(part) => Err(format!("Invalid part '{}'", part)),
+        None => Err("Missing part".to_string()),
+    }
+}
+
+fn parse_number<T: FromStr>(arg: Option<&str>, name: &str) -> Result<T, String> {
+    match arg {
+        Some(number) => number
+            .parse()
+            .map_err(|_| format!("Invalid {} '{}'", name, number)),
+        None => Err(format!("Missing {}", name)),
+    }
+}
+
+fn parse_path(arg: Option<&str>) -> Result<String, String> {
+    arg.map(str::to_string)
+        .ok_or_else(|| "Missing path".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    fn args(string: &str) -> Vec<String> {
+        string.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::Day(7),
+                part: None,
+                input: Input::default(),
+                animate: None,
+                jobs: None,
+                stats: false,
+                cache: true,
+                trace: trace::Options::default()
+            }),
+            parse(&args("run 7"))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::Day(7),
+                part: Some(2),
+                input: Input::default(),
+                animate: None,
+                jobs: None,
+                stats: false,
+                cache: true,
+                trace: trace::Options::default()
+            }),
+            parse(&args("run 7 --part 2"))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: Input::default(),
+                animate: None,
+                jobs: None,
+                stats: false,
+                cache: true,
+                trace: trace::Options::default()
+            }),
+            parse(&args("run all"))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::All,
+                part: Some(1),
+                input: Input::default(),
+                animate: None,
+                jobs: Some(4),
+                stats: true,
+                cache: false,
+                trace: trace::Options::default()
+            }),
+            parse(&args("run all --jobs 4 --stats --no-cache --part 1"))
+        );
+        assert!(parse(&args("run all --jobs 0")).is_err());
+        assert!(parse(&args("run 7 --jobs 4")).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_input() {
+        let Ok(Command::Run { input, .. }) =
+            parse(&args("run 14 --sample --params width=11,height=7"))
+        else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Source::Sample, input.source);
+        assert_eq!(Some(11), input.params.get("width"));
+
+        // The sample of day 14 brings its own area, which can still be overridden
+        let day = days::get(14).unwrap();
+        let Ok(Command::Run { input, .. }) = parse(&args("run 14 --sample --params height=9"))
+        else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(11), input.params(&day).get("width"));
+        assert_eq!(Some(9), input.params(&day).get("height"));
+
+        let Ok(Command::Run { input, .. }) = parse(&args("run 7 --input -")) else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Source::Stdin, input.source);
+
+        assert!(parse(&args("run all --input other.txt")).is_err());
+        assert!(parse(&args("run 14 --params width")).is_err());
+
+        let Ok(Command::Run { input, .. }) = parse(&args(
+            "run all --set day14.width=11 --set day4.word=\"SAMX\" --params width=12",
+        )) else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(11), input.config.params(14).get("width"));
+        assert_eq!(Some(12), input.params(&days::get(14).unwrap()).get("width"));
+        assert_eq!(
+            Some("SAMX"),
+            input.params(&days::get(4).unwrap()).text("word")
+        );
+        assert!(parse(&args("run all --set width=11")).is_err());
+
+        // Parameters have to be declared by the day, or by any day when running all of them
+        assert!(parse(&args("run 4 --sample --params word=5")).is_err());
+        assert!(parse(&args("run 14 --params widht=11")).is_err());
+        assert!(parse(&args("run all --params widht=11")).is_err());
+        assert!(parse(&args("image 14 out.ppm --params word=\"SAMX\"")).is_err());
+        assert!(parse(&args("run all --set")).is_err());
+    }
+
+    #[test]
+    fn test_run_unsolved_part() {
+        let Ok(Command::Run { part, input, .. }) = parse(&args("run 16 --part 2 --sample")) else {
+            panic!("expected a run command");
+        };
+        let day = days::get(16).unwrap();
+
+        assert!(matches!(
+            runner::run(&day, part, &input, false, None),
+            Err(runner::Error::Unsolved { day: 16, part: 2 })
+        ));
+        assert!(runner::run(&day, Some(1), &input, false, None).is_ok());
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(Ok((Input::default(), None)), parse_day(1, &args("")));
+        assert_eq!(
+            Ok(Source::Path("other.txt".to_string())),
+            parse_day(1, &args("other.txt")).map(|(input, _)| input.source)
+        );
+        assert_eq!(
+            Ok(Source::Stdin),
+            parse_day(1, &args("-")).map(|(input, _)| input.source)
+        );
+        assert_eq!(
+            Ok(Source::Sample),
+            parse_day(1, &args("--sample")).map(|(input, _)| input.source)
+        );
+        assert!(parse_day(1, &args("a.txt b.txt")).is_err());
+        assert!(parse_day(1, &args("--part 1")).is_err());
+        assert!(parse_day(14, &args("--params width=11")).is_ok());
+        assert!(parse_day(1, &args("--params width=11")).is_err());
+        assert_eq!(
+            Ok(Some(animate::Options {
+                fps: 30.0,
+                output: None
+            })),
+            parse_day(1, &args("--sample --fps 30")).map(|(_, animate)| animate)
+        );
+    }
+
+    #[test]
+    fn test_parse_run_trace() {
+        let Ok(Command::Run { trace, .. }) = parse(&args(
+            "run 15 --trace day15,search=info --trace-file trace.log",
+        )) else {
+            panic!("expected a run command");
+        };
+        assert_eq!(
+            trace::Options {
+                filter: Some("day15,search=info".parse().unwrap()),
+                file: Some("trace.log".to_string())
+            },
+            trace
+        );
+
+        assert!(parse(&args("run 15 --trace")).is_err());
+        assert!(parse(&args("run 15 --trace day15=loud")).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_animate() {
+        let Ok(Command::Run { animate, .. }) = parse(&args("run 15 --animate --frames frames.txt"))
+        else {
+            panic!("expected a run command");
+        };
+        assert_eq!(
+            Some(animate::Options {
+                fps: 10.0,
+                output: Some("frames.txt".to_string())
+            }),
+            animate
+        );
+
+        assert!(parse(&args("run all --animate")).is_err());
+        assert!(parse(&args("run 6 --fps 0")).is_err());
+        assert!(parse(&args("run 6 --frames")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            Ok(Command::Verify {
+                selection: Selection::All
+            }),
+            parse(&args("verify"))
+        );
+        assert_eq!(
+            Ok(Command::Verify {
+                selection: Selection::Day(8)
+            }),
+            parse(&args("verify 8"))
+        );
+        assert!(parse(&args("verify 8 9")).is_err());
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            Ok(Command::Fetch {
+                selection: Selection::Day(3)
+            }),
+            parse(&args("fetch 3"))
+        );
+        assert!(parse(&args("fetch")).is_err());
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            Ok(Command::Submit {
+                day: 2,
+                part: 2,
+                answer: Some("569".to_string())
+            }),
+            parse(&args("submit 2 2 569"))
+        );
+        assert_eq!(
+            Ok(Command::Submit {
+                day: 2,
+                part: 1,
+                answer: None
+            }),
+            parse(&args("submit 2 1"))
+        );
+        assert!(parse(&args("submit all 1")).is_err());
+        assert!(parse(&args("submit 2")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Ok(Command::Bench {
+                selection: Selection::All,
+                options: bench::Options::default()
+            }),
+            parse(&args("bench"))
+        );
+        assert_eq!(
+            Ok(Command::Bench {
+                selection: Selection::Day(6),
+                options: bench::Options {
+                    iterations: 3,
+                    save: None,
+                    compare: Some("baseline.json".to_string()),
+                    threshold: 0.25,
+                }
+            }),
+            parse(&args(
+                "bench 6 --iterations 3 --compare baseline.json --threshold 25"
+            ))
+        );
+        assert!(parse(&args("bench --iterations many")).is_err());
+        assert!(parse(&args("bench --save")).is_err());
+    }
+
+    #[test]
+    fn test_parse_image() {
+        assert_eq!(
+            Ok(Command::Image {
+                day: 14,
+                path: "tree.pgm".to_string(),
+                scale: 4,
+                input: Input::default()
+            }),
+            parse(&args("image 14 tree.pgm --scale 4"))
+        );
+        let Ok(Command::Image { input, scale, .. }) = parse(&args("image 16 maze.svg --sample"))
+        else {
+            panic!("expected an image command");
+        };
+        assert_eq!((Source::Sample, 1), (input.source, scale));
+
+        assert!(parse(&args("image all tree.svg")).is_err());
+        assert!(parse(&args("image 14 tree.png")).is_err());
+        assert!(parse(&args("image 14 tree.svg --scale 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(Ok(Command::New { day: 17 }), parse(&args("new 17")));
+        assert!(parse(&args("new")).is_err());
+        assert!(parse(&args("new 26")).is_err());
+        assert!(parse(&args("new 17 18")).is_err());
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(
+            Ok(Command::Status { markdown: false }),
+            parse(&args("status"))
+        );
+        assert_eq!(
+            Ok(Command::Status { markdown: true }),
+            parse(&args("status --markdown"))
+        );
+        assert!(parse(&args("status --html")).is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        assert_eq!(Ok(Command::ClearCache), parse(&args("cache clear")));
+        assert!(parse(&args("cache")).is_err());
+        assert!(parse(&args("cache fill")).is_err());
+        assert!(parse(&args("cache clear 7")).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(&args("")).is_err());
+        assert!(parse(&args("walk 7")).is_err());
+        assert!(parse(&args("run")).is_err());
+        assert!(parse(&args("run 42")).is_err());
+        assert!(parse(&args("run 7 --part 3")).is_err());
+        assert!(parse(&args("run 7 --verbose")).is_err());
+    }
+}
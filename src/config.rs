@@ -0,0 +1,376 @@
+//! Per-day puzzle parameters read from `aoc.toml`, to explore variants of the puzzles without
+//! recompiling. Each day has a section of `key = value` lines, where values are integers or
+//! double quoted text and lines starting with `#` are comments:
+//!
+//! ```toml
+//! # The sample's area
+//! [day14]
+//! width = 11
+//! height = 7
+//!
+//! [day4]
+//! word = "SAMX"
+//! ```
+//!
+//! Single parameters can be overridden on the command line with `--set day14.width=11`. Both
+//! only accept the parameters the day declares in [`Solution::PARAMS`], so that misspelled keys
+//! and values of the wrong type are reported instead of ignored. The same goes for `--params`,
+//! through [`check_params`].
+//!
+//! [`Solution::PARAMS`]: crate::solution::Solution::PARAMS
+
+use crate::days;
+use crate::parse::{self, ParseError};
+use crate::solution::{Param, ParamKind, Params};
+use std::fs;
+
+pub const PATH: &str = "aoc.toml";
+
+const DAY: &str = "a day from 1 to 25";
+const VALUE: &str = "an integer or double quoted text";
+
+/// Why a day doesn't accept a parameter
+enum Invalid {
+    /// Description of the keys the day accepts
+    Key(String),
+    /// Description of the values the key takes
+    Value(&'static str),
+}
+
+/// Checks that the day declares the parameter with the type of the value
+fn check(day: u8, key: &str, value: &Param) -> Result<(), Invalid> {
+    let accepted = days::get(day).map(|day| day.params).unwrap_or_default();
+    match accepted.iter().find(|(name, _)| *name == key) {
+        Some((_, kind)) if *kind == ParamKind::of(value) => Ok(()),
+        Some((_, kind)) => Err(Invalid::Value(kind.expected())),
+        None if accepted.is_empty() => Err(Invalid::Key(format!(
+            "a parameter of day {}, which has none",
+            day
+        ))),
+        None => {
+            let names: Vec<_> = accepted.iter().map(|(name, _)| *name).collect();
+            Err(Invalid::Key(format!(
+                "a parameter of day {} ({})",
+                day,
+                names.join(", ")
+            )))
+        }
+    }
+}
+
+/// Checks parameters given to several days at once, like those of `--params`, each of which has
+/// to be declared by at least one of the days with the type of its value
+pub fn check_params(days: &[u8], params: &Params) -> Result<(), String> {
+    for (key, value) in params.iter() {
+        let errors: Vec<Invalid> = days
+            .iter()
+            .filter_map(|&day| check(day, key, value).err())
+            .collect();
+        if errors.len() < days.len() {
+            continue;
+        }
+
+        let wrong_type = errors.iter().find_map(|error| match error {
+            Invalid::Value(expected) => Some(*expected),
+            Invalid::Key(_) => None,
+        });
+        return Err(match (wrong_type, errors.into_iter().next()) {
+            (Some(expected), _) => format!("Invalid value of '{}', expected {}", key, expected),
+            (None, Some(Invalid::Key(expected))) if days.len() == 1 => {
+                format!("Invalid parameter '{}', expected {}", key, expected)
+            }
+            _ => format!(
+                "Invalid parameter '{}', expected a parameter of any day",
+                key
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Parameters of each day, of which each day only sees its own
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Config {
+    days: Vec<(u8, Params)>,
+}
+
+impl Config {
+    pub fn from_string(string: &str) -> Result<Self, ParseError> {
+        let mut config = Config::default();
+        let mut day = None;
+
+        for line in parse::lines(string) {
+            let text = line.text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = text.strip_prefix('[') {
+                let (name, rest) = line.split_once(section, "]")?;
+                if !rest.trim().is_empty() {
+                    return Err(line.expected(rest.trim(), "nothing after the section"));
+                }
+                let number = line.strip_prefix(name, "day")?;
+                day = Some(
+                    number
+                        .parse()
+                        .ok()
+                        .filter(|number| (1..=25).contains(number))
+                        .ok_or_else(|| line.expected(number, DAY))?,
+                );
+                continue;
+            }
+
+            let Some(day) = day else {
+                return Err(line.expected(text, "a [dayN] section"));
+            };
+            let (key, value) = line.split_once(text, "=")?;
+            if key.trim().is_empty() {
+                return Err(line.expected(key, "a parameter name"));
+            }
+            let (key, value) = (key.trim(), value.trim());
+            let param = line.parse(value, VALUE)?;
+            match check(day, key, &param) {
+                Ok(()) => config.set(day, key, param),
+                Err(Invalid::Key(expected)) => return Err(line.expected(key, &expected)),
+                Err(Invalid::Value(expected)) => return Err(line.expected(value, expected)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the config file, which is considered empty when it does not exist
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(PATH) {
+            Ok(string) => Self::from_string(&string)
+                .map_err(|error| format!("Failed to parse {}: {}", PATH, error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Failed to read {}: {}", PATH, error)),
+        }
+    }
+
+    fn day_mut(&mut self, day: u8) -> &mut Params {
+        let index = match self.days.iter().position(|(number, _)| *number == day) {
+            Some(index) => index,
+            None => {
+                self.days.push((day, Params::default()));
+                self.days.len() - 1
+            }
+        };
+
+        &mut self.days[index].1
+    }
+
+    pub fn set(&mut self, day: u8, key: &str, value: Param) {
+        self.day_mut(day).insert(key, value);
+    }
+
+    /// Sets a parameter given as `dayN.key=value`, like the `--set` option
+    pub fn set_arg(&mut self, arg: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid setting '{}', expected dayN.key=value", arg);
+        let (name, value) = arg.split_once('=').ok_or_else(invalid)?;
+        let (day, key) = name.split_once('.').ok_or_else(invalid)?;
+        let day = day
+            .strip_prefix("day")
+            .and_then(|number| number.parse().ok())
+            .filter(|number| (1..=25).contains(number))
+            .ok_or_else(|| format!("Invalid day '{}' of '{}', expected day1 to day25", day, arg))?;
+        if key.is_empty() {
+            return Err(invalid());
+        }
+
+        let param = value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' of '{}'", value, name))?;
+        match check(day, key, &param) {
+            Ok(()) => self.set(day, key, param),
+            Err(Invalid::Key(expected)) => {
+                return Err(format!(
+                    "Invalid parameter '{}', expected {}",
+                    name, expected
+                ))
+            }
+            Err(Invalid::Value(expected)) => {
+                return Err(format!(
+                    "Invalid value '{}' of '{}', expected {}",
+                    value, name, expected
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the parameters of the other config, which take precedence over these
+    pub fn extend(&mut self, other: &Config) {
+        for (day, params) in &other.days {
+            self.day_mut(*day).extend(params);
+        }
+    }
+
+    /// Parameters of the given day, empty if it has none
+    pub fn params(&self, day: u8) -> Params {
+        self.days
+            .iter()
+            .find(|(number, _)| *number == day)
+            .map(|(_, params)| params.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+# Variants of the puzzles
+[day14]
+width = 11
+height = 7
+
+[day4]
+word = \"SAMX\"
+";
+
+    #[test]
+    fn test_from_string() {
+        let config = Config::from_string(CONFIG).unwrap();
+
+        assert_eq!(Some(11), config.params(14).get("width"));
+        assert_eq!(Some(7), config.params(14).get("height"));
+        assert_eq!(Some("SAMX"), config.params(4).text("word"));
+        assert_eq!(None, config.params(4).get("width"));
+        assert_eq!(Params::default(), config.params(1));
+        assert_eq!(Config::default(), Config::from_string("").unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            ParseError::new(1, 1, "expected a [dayN] section, found 'width = 11'"),
+            Config::from_string("width = 11").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(1, 5, "expected a day from 1 to 25, found '26'"),
+            Config::from_string("[day26]").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(
+                2,
+                9,
+                "expected an integer or double quoted text, found 'wide'"
+            ),
+            Config::from_string("[day14]\nwidth = wide").unwrap_err()
+        );
+        assert!(Config::from_string("[day14]\nwidth 11").is_err());
+        assert!(Config::from_string("[day14\n").is_err());
+        assert!(Config::from_string("[part1]\n").is_err());
+    }
+
+    #[test]
+    fn test_undeclared_params() {
+        assert_eq!(
+            ParseError::new(
+                2,
+                1,
+                "expected a parameter of day 14 (width, height, seconds), found 'widht'"
+            ),
+            Config::from_string("[day14]\nwidht = 11").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(2, 9, "expected an integer, found '\"11\"'"),
+            Config::from_string("[day14]\nwidth = \"11\"").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(2, 8, "expected double quoted text, found '4'"),
+            Config::from_string("[day4]\nword = 4").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(
+                2,
+                1,
+                "expected a parameter of day 1, which has none, found 'width'"
+            ),
+            Config::from_string("[day1]\nwidth = 11").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_check_params() {
+        let params = |string: &str| string.parse::<Params>().unwrap();
+
+        assert_eq!(Ok(()), check_params(&[14], &params("width=11,seconds=5")));
+        assert_eq!(
+            Err(
+                "Invalid parameter 'widht', expected a parameter of day 14 (width, height, \
+                 seconds)"
+                    .to_string()
+            ),
+            check_params(&[14], &params("widht=11"))
+        );
+        assert_eq!(
+            Err("Invalid value of 'word', expected double quoted text".to_string()),
+            check_params(&[4], &params("word=5"))
+        );
+
+        // Parameters of all days only need to be declared by one of them
+        assert_eq!(
+            Ok(()),
+            check_params(&[4, 14], &params("width=11,word=\"SAMX\""))
+        );
+        assert_eq!(
+            Err("Invalid parameter 'depth', expected a parameter of any day".to_string()),
+            check_params(&[4, 14], &params("depth=3"))
+        );
+        assert!(check_params(&[4, 14], &params("width=\"11\"")).is_err());
+    }
+
+    #[test]
+    fn test_declared_sample_params() {
+        for day in days::registry() {
+            for (key, value) in day.sample_params {
+                assert!(
+                    check(day.number, key, &Param::Integer(*value)).is_ok(),
+                    "{}",
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_arg() {
+        let mut config = Config::from_string(CONFIG).unwrap();
+        let mut overrides = Config::default();
+        overrides.set_arg("day14.width=101").unwrap();
+        overrides.set_arg("day4.word=\"XMAS\"").unwrap();
+        config.extend(&overrides);
+
+        assert_eq!(Some(101), config.params(14).get("width"));
+        assert_eq!(Some(7), config.params(14).get("height"));
+        assert_eq!(Some("XMAS"), config.params(4).text("word"));
+
+        for invalid in [
+            "width=11",
+            "day14.width",
+            "day0.width=11",
+            "day14.=11",
+            "day14.width=x",
+            "day14.widht=11",
+            "day14.width=\"11\"",
+            "day4.word=4",
+            "day1.width=11",
+        ] {
+            assert!(overrides.set_arg(invalid).is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            Err(
+                "Invalid parameter 'day14.widht', expected a parameter of day 14 (width, height, \
+                 seconds)"
+                    .to_string()
+            ),
+            overrides.set_arg("day14.widht=11")
+        );
+    }
+}
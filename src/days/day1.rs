@@ -0,0 +1,153 @@
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct Problem {
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+impl Problem {
+    fn from_string(string: String) -> Result<Self, ParseError> {
+        let (left, right) = parse::lines(&string)
+            .map(|line| {
+                let mut fields = line.fields();
+                Ok((
+                    fields.parse::<u32>("a location ID")?,
+                    fields.parse::<u32>("a location ID")?,
+                ))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?
+            .into_iter()
+            .unzip();
+
+        Ok(Self { left, right })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 1;
+    const SAMPLE: &'static str = include_str!("../../samples/day1.txt");
+
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input.to_string())
+    }
+
+    /// Sums in `u64`, as the distances of a few IDs near `u32::MAX` already overflow a `u32`
+    fn part_1(&self) -> u64 {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+
+        left.sort();
+        right.sort();
+
+        left.iter()
+            .zip(right.iter())
+            .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+            .sum()
+    }
+
+    fn part_2(&self) -> Option<u64> {
+        Some(
+            self.left
+                .iter()
+                .map(|&left| {
+                    u64::from(left)
+                        * self.right.iter().filter(|&&right| left == right).count() as u64
+                })
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(
+            11,
+            Problem::from_string(Problem::SAMPLE.to_string())
+                .unwrap()
+                .part_1()
+        );
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(
+            Some(31),
+            Problem::from_string(Problem::SAMPLE.to_string())
+                .unwrap()
+                .part_2()
+        );
+    }
+
+    #[test]
+    fn test_large_ids() {
+        let problem = Problem::from_string("4000000000   4000000000\n".repeat(2)).unwrap();
+        assert_eq!((0, Some(16000000000)), (problem.part_1(), problem.part_2()));
+
+        let problem = Problem::from_string("0   4000000000\n".repeat(2)).unwrap();
+        assert_eq!(8000000000, problem.part_1());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Problem::from_string("3   4\n4".to_string()).err().unwrap();
+
+        assert_eq!((2, 2), (error.line, error.column));
+        assert_eq!("expected a location ID, found end of line", error.message);
+    }
+
+    #[test]
+    fn test_property_reference() {
+        property::check(
+            |rng| {
+                rng.vec(0..=30, |rng| {
+                    (rng.range(0..=20) as u32, rng.range(0..=20) as u32)
+                })
+            },
+            |pairs| {
+                let input: String = pairs
+                    .iter()
+                    .map(|(a, b)| format!("{}   {}\n", a, b))
+                    .collect();
+                let problem = Problem::from_string(input).unwrap();
+                let (mut left, mut right): (Vec<u32>, Vec<u32>) = pairs.iter().cloned().unzip();
+
+                // Pair up the smallest remaining numbers one by one
+                let mut distance = 0;
+                while let (Some(a), Some(b)) =
+                    (left.iter().min().copied(), right.iter().min().copied())
+                {
+                    left.remove(left.iter().position(|&n| n == a).unwrap());
+                    right.remove(right.iter().position(|&n| n == b).unwrap());
+                    distance += u64::from(a.abs_diff(b));
+                }
+
+                // Count the occurrences of each number in the right list up front
+                let counts = right_counts(pairs);
+                let similarity = pairs
+                    .iter()
+                    .map(|(a, _)| u64::from(a * counts.get(a).copied().unwrap_or(0)))
+                    .sum();
+
+                property::equal(problem.part_1(), distance)?;
+                property::equal(problem.part_2(), Some(similarity))
+            },
+        );
+    }
+
+    fn right_counts(pairs: &[(u32, u32)]) -> HashMap<u32, u32> {
+        pairs.iter().fold(HashMap::new(), |mut counts, (_, b)| {
+            *counts.entry(*b).or_default() += 1;
+            counts
+        })
+    }
+}
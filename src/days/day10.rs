@@ -0,0 +1,317 @@
+use crate::geometry::Vec2;
+use crate::grid::Grid;
+use crate::image::{Picture, Render};
+use crate::parse::{self, ParseError};
+use crate::search::{self, Search};
+use crate::solution::Solution;
+
+type Position = Vec2<u32>;
+
+#[derive(Debug)]
+struct Map {
+    heights: Grid<u8>,
+}
+
+impl Map {
+    fn from_string(string: &str) -> Result<Map, ParseError> {
+        Ok(Self {
+            heights: parse::grid(string, |char| char.to_digit(10).unwrap_or(127) as u8)?,
+        })
+    }
+
+    fn height_at(&self, pos: Position) -> u8 {
+        self.heights[(pos.x as usize, pos.y as usize)]
+    }
+
+    fn positions_of_height(&self, of_height: u8) -> Vec<Position> {
+        self.heights
+            .find_all(&of_height)
+            .map(|(x, y)| Vec2::new(x as u32, y as u32))
+            .collect()
+    }
+
+    fn trailheads(&self) -> Vec<Position> {
+        self.positions_of_height(0)
+    }
+
+    fn peaks(&self) -> Vec<Position> {
+        self.positions_of_height(9)
+    }
+
+    fn neighbours_of(&self, pos: Position) -> Vec<Position> {
+        self.heights
+            .neighbours_4(pos.x as usize, pos.y as usize)
+            .map(|(x, y)| Vec2::new(x as u32, y as u32))
+            .collect()
+    }
+
+    /// All hiking trails from the given position, which only ever go up by a height of one. As
+    /// every trail to a position has the same length, all of them are cheapest paths.
+    fn trails_from(&self, from: Position) -> Search<Position, usize> {
+        search::bfs(
+            from,
+            |&position| {
+                let height = self.height_at(position);
+                self.neighbours_of(position)
+                    .into_iter()
+                    .filter(move |&next| self.height_at(next) == height + 1)
+            },
+            |_| false,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    map: Map,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::from_string(string)?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 10;
+    const SAMPLE: &'static str = include_str!("../../samples/day10.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        let peaks = self.map.peaks();
+
+        self.map
+            .trailheads()
+            .iter()
+            .map(|trailhead_pos| {
+                let trails = self.map.trails_from(*trailhead_pos);
+                // For each trailhead check if this peak was reached
+                peaks
+                    .iter()
+                    .filter(|peak_pos| trails.costs.contains_key(peak_pos))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        let peaks = self.map.peaks();
+
+        Some(
+            self.map
+                .trailheads()
+                .iter()
+                .map(|trailhead_pos| {
+                    let trails = self.map.trails_from(*trailhead_pos);
+                    peaks
+                        .iter()
+                        .map(|peak_pos| trails.path_count(*peak_pos))
+                        .sum::<usize>()
+                })
+                .sum(),
+        )
+    }
+}
+
+impl Render for Problem {
+    /// Height map in greyscale, from black at the trailheads to white at the peaks
+    fn render(&self) -> Picture {
+        Picture::Gray(self.map.heights.map(|&height| match height {
+            0..=9 => (height as u32 * 255 / 9) as u8,
+            _ => 0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+    use std::collections::HashSet;
+
+    fn example(name: &str) -> String {
+        examples::input(10, name).unwrap()
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            2,
+            Problem::from_string(&example("part1_simplified1"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(
+            4,
+            Problem::from_string(&example("part1_simplified2"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(36, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(3),
+            Problem::from_string(&example("part2_simplified1"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(13),
+            Problem::from_string(&example("part2_simplified2"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(81),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let Picture::Gray(levels) = Problem::from_string(&example("part1_simplified1"))
+            .unwrap()
+            .render()
+        else {
+            panic!("expected a greyscale picture");
+        };
+
+        // Impassable tiles are black, just like trailheads
+        assert_eq!(Some(&[0; 7][..]), levels.row(0));
+        assert_eq!(Some(&[170, 141, 113, 85, 113, 141, 170][..]), levels.row(3));
+        assert_eq!(Some(&255), levels.get(6, 6));
+    }
+
+    #[test]
+    fn test_map_neighbours() {
+        let map = Map::from_string(Problem::SAMPLE).unwrap();
+        assert_eq!(
+            vec![Vec2::new(1, 0), Vec2::new(0, 1)],
+            map.neighbours_of(Vec2::new(0, 0))
+        );
+        assert_eq!(
+            vec![
+                Vec2::new(0, 1),
+                Vec2::new(2, 1),
+                Vec2::new(1, 0),
+                Vec2::new(1, 2)
+            ],
+            map.neighbours_of(Vec2::new(1, 1))
+        );
+        assert_eq!(
+            vec![Vec2::new(6, 7), Vec2::new(7, 6)],
+            map.neighbours_of(Vec2::new(7, 7))
+        );
+    }
+
+    #[test]
+    fn test_ragged_map() {
+        // Found by fuzzing, rows shorter than the first one used to shift the rest of the map
+        assert_eq!(
+            ParseError::new(
+                2,
+                8,
+                "expected 8 cells like the first row, found end of line"
+            ),
+            Problem::from_string("89010123\n7812187\n87430965")
+                .err()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Follows every trail going up by one, collecting the peaks at their ends
+        fn trails(
+            heights: &[Vec<Option<u32>>],
+            (x, y): (usize, usize),
+            peaks: &mut Vec<(usize, usize)>,
+        ) {
+            let height = heights[y][x];
+            if height == Some(9) {
+                peaks.push((x, y));
+            }
+            let neighbours = [
+                (x + 1, y),
+                (x, y + 1),
+                (x.wrapping_sub(1), y),
+                (x, y.wrapping_sub(1)),
+            ];
+            for (next_x, next_y) in neighbours {
+                let next = heights
+                    .get(next_y)
+                    .and_then(|row| row.get(next_x))
+                    .copied()
+                    .flatten();
+                if next.is_some() && next == height.map(|height| height + 1) {
+                    trails(heights, (next_x, next_y), peaks);
+                }
+            }
+        }
+
+        property::check(
+            |rng| {
+                let width = rng.range(1..=10) as u8;
+                let cells = rng.vec(1..=100, |rng| match rng.below(8) {
+                    0..=4 => 0,
+                    offset => offset as u8 - 4,
+                });
+                (width, cells)
+            },
+            |(width, cells)| {
+                // Heights go up along the diagonals, with some offsets and gaps, so that trails
+                // reach the peaks now and then
+                let width = (*width as usize).max(1);
+                let heights: Vec<Vec<Option<u32>>> = cells
+                    .chunks_exact(width)
+                    .enumerate()
+                    .map(|(y, row)| {
+                        let height = |(x, &cell): (usize, &u8)| match cell {
+                            0..=2 => Some((x + y + [0, 1, 9][cell as usize]) as u32 % 10),
+                            _ => None,
+                        };
+                        row.iter().enumerate().map(height).collect()
+                    })
+                    .collect();
+                if heights.is_empty() {
+                    return Ok(());
+                }
+                let input: String = heights
+                    .iter()
+                    .map(|row| {
+                        let tile = |height: &Option<u32>| {
+                            height.map_or('.', |height| char::from_digit(height, 10).unwrap())
+                        };
+                        row.iter().map(tile).collect::<String>() + "\n"
+                    })
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+
+                let (mut scores, mut ratings) = (0, 0);
+                for (y, row) in heights.iter().enumerate() {
+                    for x in (0..width).filter(|&x| row[x] == Some(0)) {
+                        let mut peaks = vec![];
+                        trails(&heights, (x, y), &mut peaks);
+                        ratings += peaks.len();
+                        scores += peaks.into_iter().collect::<HashSet<_>>().len();
+                    }
+                }
+
+                property::equal(problem.part_1(), scores)?;
+                property::equal(problem.part_2(), Some(ratings))
+            },
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::metrics;
+use crate::parse::{self, ParseError};
+use crate::solution::{ParamKind, Params, Solution};
+use std::collections::HashMap;
+
+/// Engraved numbers are read as `u64` but stored as `u128`, as a stone with an odd number of
+/// digits gains at most 7 digits before it splits again, which would overflow a `u64`
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+struct Stone {
+    number: u128,
+}
+
+impl Stone {
+    fn new(number: u128) -> Self {
+        Self { number }
+    }
+
+    fn blink(&self) -> Stones {
+        if self.number == 0 {
+            return Stones::from_slice(&[Self::new(1)]);
+        }
+
+        let string = self.number.to_string();
+
+        if string.len().is_multiple_of(2) {
+            let split_at = string.len() / 2;
+            return Stones::from_slice(&[
+                Self::new(string[0..split_at].parse().unwrap()),
+                Self::new(string[split_at..].parse().unwrap()),
+            ]);
+        }
+
+        Stones::from_slice(&[Self::new(self.number * 2024)])
+    }
+}
+
+#[derive(Debug)]
+struct Stones {
+    elements: Vec<Stone>,
+}
+
+impl Stones {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            elements: parse::lines(string)
+                .flat_map(|line| {
+                    line.text.split_whitespace().map(move |stone| {
+                        Ok(Stone::new(
+                            line.parse::<u64>(stone, "a stone number")?.into(),
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn from_slice(list: &[Stone]) -> Self {
+        Self {
+            elements: list.to_vec(),
+        }
+    }
+
+    fn blink_count(&self, times: u32) -> u128 {
+        if times == 0 {
+            return self.elements.len() as u128;
+        }
+
+        let mut cache = HashMap::new();
+        self.blink_count_cached(times, &mut cache)
+    }
+
+    fn blink_count_cached(
+        &self,
+        blink_times: u32,
+        cache: &mut HashMap<Stone, HashMap<u32, u128>>,
+    ) -> u128 {
+        self.elements
+            .iter()
+            .map(|&stone| {
+                let cached_stone_counts = cache.entry(stone).or_default();
+
+                // Check if this stone has been blinked before this many times, if so re-use count
+                if let Some(&cached_count) = cached_stone_counts.get(&blink_times) {
+                    metrics::count("day11.cache_hits");
+                    return cached_count;
+                }
+                metrics::count("day11.cache_misses");
+
+                // Stone has not been blinked this many times, so let's do it!
+                let blink_stones = stone.blink();
+
+                // Count and cache the number of stones after blinking (recursive case + special case)
+                if blink_times > 1 {
+                    let count = blink_stones.blink_count_cached(blink_times - 1, cache);
+                    cache.get_mut(&stone).unwrap().insert(blink_times, count);
+                    count
+                } else {
+                    let count = blink_stones.elements.len() as u128;
+                    cached_stone_counts.insert(blink_times, count);
+                    count
+                }
+            })
+            .sum()
+    }
+}
+
+/// Number of times the stones are blinked at in each part
+const BLINKS: [u32; 2] = [25, 75];
+
+/// Most blinks that can be asked for, as each blink is a level of recursion and the number of
+/// stones grows exponentially
+pub const MAX_BLINKS: u32 = 150;
+
+#[derive(Debug)]
+pub struct Problem {
+    stones: Stones,
+    blinks: [u32; 2],
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            stones: Stones::from_string(string)?,
+            blinks: BLINKS,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 11;
+    const SAMPLE: &'static str = include_str!("../../samples/day11.txt");
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[
+        ("blinks_1", ParamKind::Integer),
+        ("blinks_2", ParamKind::Integer),
+    ];
+
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    /// The number of blinks of each part can be overridden by the `blinks_1` and `blinks_2`
+    /// parameters, up to [`MAX_BLINKS`]
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let mut problem = Self::from_string(input)?;
+        for (blinks, key) in problem.blinks.iter_mut().zip(["blinks_1", "blinks_2"]) {
+            if let Some(value) = params.get_in(key, 0..=MAX_BLINKS)? {
+                *blinks = value;
+            }
+        }
+
+        Ok(problem)
+    }
+
+    fn part_1(&self) -> u128 {
+        self.stones.blink_count(self.blinks[0])
+    }
+
+    fn part_2(&self) -> Option<u128> {
+        Some(self.stones.blink_count(self.blinks[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            55312,
+            Problem::from_string(Problem::SAMPLE).unwrap().part_1()
+        );
+
+        // The puzzle description also lists the stones after 6 blinks
+        let params = "blinks_1=6,blinks_2=0".parse().unwrap();
+        let problem = Problem::parse_with(Problem::SAMPLE, &params).unwrap();
+        assert_eq!((22, Some(2)), (problem.part_1(), problem.part_2()));
+
+        // Counts of many more blinks than the puzzle asks for still fit
+        let params = "blinks_2=150".parse().unwrap();
+        let problem = Problem::parse_with(Problem::SAMPLE, &params).unwrap();
+        assert!(problem.part_2().unwrap() > u64::MAX as u128);
+
+        for blinks in ["blinks_2=-1", "blinks_1=151"] {
+            let error = Problem::parse_with(Problem::SAMPLE, &blinks.parse().unwrap()).unwrap_err();
+            assert_eq!(0, error.line);
+        }
+    }
+
+    #[test]
+    fn test_large_stone() {
+        // Multiplying this stone by 2024 overflows a u64
+        let problem = Problem::from_string("9999999999999").unwrap();
+        assert_eq!(
+            (20830, Some(25061216641386)),
+            (problem.part_1(), problem.part_2())
+        );
+    }
+
+    #[test]
+    fn test_property_reference() {
+        property::check(
+            |rng| {
+                (
+                    rng.vec(1..=5, |rng| match rng.bool() {
+                        true => rng.range(0..=20) as u64,
+                        false => rng.range(0..=1_000_000) as u64,
+                    }),
+                    rng.range(1..=12) as u32,
+                )
+            },
+            |(numbers, blinks)| {
+                if numbers.is_empty() || *blinks == 0 {
+                    return Ok(());
+                }
+
+                let input: Vec<String> = numbers.iter().map(u64::to_string).collect();
+                let stones = Stones::from_string(&input.join(" ")).unwrap();
+
+                // Apply the rules to the whole row of stones
+                let mut row = numbers.clone();
+                for _ in 0..*blinks {
+                    row = row
+                        .iter()
+                        .flat_map(|&number| {
+                            let digits = number.to_string();
+                            if number == 0 {
+                                vec![1]
+                            } else if digits.len() % 2 == 0 {
+                                let (left, right) = digits.split_at(digits.len() / 2);
+                                vec![left.parse().unwrap(), right.parse().unwrap()]
+                            } else {
+                                vec![number * 2024]
+                            }
+                        })
+                        .collect();
+                }
+
+                property::equal(stones.blink_count(*blinks), row.len() as u128)
+            },
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use crate::geometry::Vec2;
+use crate::grid::Grid;
+use crate::image::{Picture, Render, Rgb};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+struct Region {
+    plant: char,
+    positions: HashSet<Vec2<i32>>,
+}
+
+impl Region {
+    fn new(plant: char) -> Self {
+        Self {
+            plant,
+            positions: HashSet::new(),
+        }
+    }
+
+    fn perimeter(&self) -> u32 {
+        let shared_borders: u32 = self
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(index, &pos)| {
+                self.positions
+                    .iter()
+                    .skip(index + 1)
+                    .filter(|&&other| pos != other && pos.is_adjacent(other))
+                    .count() as u32
+            })
+            .sum();
+
+        self.positions.len() as u32 * 4 - 2 * shared_borders
+    }
+
+    /// Returns the number of corners the region has, which is the same as the number of sides
+    fn sides(&self) -> u32 {
+        self.positions
+            .iter()
+            // Create hashmap mapping each of the four corners of a plant to the plant that touches
+            // this corner. Corner coordinates are expanded from the plant coordinate, so plant
+            // (x,y) has corners (x,y), (x,y+1), (x+1,y), (x+1,y+1).
+            .fold(
+                HashMap::new(),
+                |mut acc: HashMap<Vec2<i32>, Vec<Vec2<i32>>>, &plant_pos| {
+                    acc.entry(plant_pos).or_default().push(plant_pos);
+                    acc.entry(Vec2::new(plant_pos.x, plant_pos.y + 1))
+                        .or_default()
+                        .push(plant_pos);
+                    acc.entry(Vec2::new(plant_pos.x + 1, plant_pos.y))
+                        .or_default()
+                        .push(plant_pos);
+                    acc.entry(Vec2::new(plant_pos.x + 1, plant_pos.y + 1))
+                        .or_default()
+                        .push(plant_pos);
+
+                    acc
+                },
+            )
+            .values()
+            .map(|plants| match plants.len() {
+                // Count corners shared with only one plant or by three plants 1, these respectively
+                // represent the outside and inside corners of the region.
+                1 | 3 => 1,
+                // Corners shared by two plants should be counted twice as an outside corner if
+                // those plants are diagonal to each other (this is the special case of diagonal
+                // regions that was mentioned in the instructions).
+                2 if plants[0].x.abs_diff(plants[1].x) == 1
+                    && plants[0].y.abs_diff(plants[1].y) == 1 =>
+                {
+                    2
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn area(&self) -> u32 {
+        self.positions.len() as u32
+    }
+
+    fn price(&self) -> u32 {
+        self.perimeter() * self.area()
+    }
+
+    fn discounted_price(&self) -> u32 {
+        self.sides() * self.area()
+    }
+
+    fn is_adjacent_pos(&self, pos: Vec2<i32>) -> bool {
+        self.positions
+            .iter()
+            .any(|plant_pos| plant_pos.is_adjacent(pos))
+    }
+}
+
+/// Garden plots, where cells without a plant pad rows shorter than the first
+#[derive(Debug)]
+struct Plot {
+    plants: Grid<Option<char>>,
+}
+
+impl Plot {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let width = string.lines().next().map_or(0, |line| line.chars().count());
+        let padded: String = string
+            .lines()
+            .map(|line| format!("{:\0<width$}\n", line))
+            .collect();
+
+        Ok(Self {
+            plants: parse::grid(&padded, |char| (char != '\0').then_some(char))?,
+        })
+    }
+
+    fn regions(&self) -> Vec<Region> {
+        let regions: Vec<Region> = self
+            .plants
+            .iter()
+            .filter_map(|(position, plant)| Some((position, (*plant)?)))
+            .fold(Vec::new(), |mut regions, ((x, y), plant)| {
+                let pos = Vec2::new(x as i32, y as i32);
+
+                let adjacent_regions: Vec<(usize, Region)> = regions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, region)| region.plant == plant && region.is_adjacent_pos(pos))
+                    .map(|(index, region)| (index, region.clone()))
+                    .collect();
+
+                let mut region = if adjacent_regions.is_empty() {
+                    Region::new(plant)
+                } else {
+                    // Multiple adjacent regions to join, so the plant is effectively connecting two
+                    // or more region into one new big region. So let's remove the old regions first.
+                    adjacent_regions.iter().rev().for_each(|(index, _)| {
+                        regions.remove(*index);
+                    });
+
+                    // Next create one big new region containing all plants of previous regions
+                    Region {
+                        plant,
+                        positions: adjacent_regions
+                            .iter()
+                            .flat_map(|(_, adjacent_region)| adjacent_region.positions.clone())
+                            .collect(),
+                    }
+                };
+
+                region.positions.insert(pos);
+                regions.push(region);
+
+                regions
+            });
+
+        regions
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    plot: Plot,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            plot: Plot::from_string(string)?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 12;
+    const SAMPLE: &'static str = include_str!("../../samples/day12.txt");
+
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> u32 {
+        self.plot
+            .regions()
+            .iter()
+            .map(|region| region.price())
+            .sum()
+    }
+
+    fn part_2(&self) -> Option<u32> {
+        Some(
+            self.plot
+                .regions()
+                .iter()
+                .map(|region| region.discounted_price())
+                .sum(),
+        )
+    }
+}
+
+impl Render for Problem {
+    /// Garden plots coloured by plant
+    fn render(&self) -> Picture {
+        Picture::Color(self.plot.plants.map(|plant| match plant {
+            Some(plant) => Rgb::category(*plant as usize),
+            None => Rgb::BLACK,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+
+    fn example(name: &str) -> String {
+        examples::input(12, name).unwrap()
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            140,
+            Problem::from_string(&example("simplified1"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(
+            772,
+            Problem::from_string(&example("simplified2"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(
+            1930,
+            Problem::from_string(Problem::SAMPLE).unwrap().part_1()
+        );
+    }
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(80),
+            Problem::from_string(&example("simplified1"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(436),
+            Problem::from_string(&example("simplified2"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(236),
+            Problem::from_string(&example("simplified3"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(368),
+            Problem::from_string(&example("simplified4"))
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(
+            Some(1206),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let Picture::Color(colors) = Problem::from_string(&example("simplified1"))
+            .unwrap()
+            .render()
+        else {
+            panic!("expected a colour picture");
+        };
+
+        assert_eq!(Some(&Rgb::category('B' as usize)), colors.get(0, 1));
+        assert_ne!(colors.get(0, 0), colors.get(0, 1));
+        assert_eq!(colors.get(2, 1), colors.get(3, 3));
+    }
+
+    #[test]
+    fn test_region_sides() {
+        assert_eq!(4, Plot::from_string("AAA").unwrap().regions()[0].sides());
+        assert_eq!(
+            4,
+            Plot::from_string("A\nA\nA").unwrap().regions()[0].sides()
+        );
+        assert_eq!(4, Plot::from_string("AA\nAA").unwrap().regions()[0].sides());
+        assert_eq!(6, Plot::from_string("AA\nA").unwrap().regions()[0].sides());
+        assert_eq!(6, Plot::from_string("AAA\nA").unwrap().regions()[0].sides());
+        // Rows longer than the first don't fit the plot
+        assert!(Plot::from_string("A\nAA").is_err());
+
+        let plot = Plot::from_string(
+            "RRRR..\n\
+             ..RRR.\n\
+             ..R...",
+        )
+        .unwrap();
+        assert_eq!('R', plot.regions()[1].plant);
+        assert_eq!(10, plot.regions()[1].sides());
+    }
+
+    #[test]
+    fn test_property_reference() {
+        const PLANTS: [char; 3] = ['A', 'B', 'C'];
+
+        property::check(
+            |rng| {
+                let width = rng.range(1..=6) as u8;
+                (width, rng.vec(1..=36, |rng| rng.below(PLANTS.len()) as u8))
+            },
+            |(width, cells)| {
+                let width = (*width as usize).max(1) as i32;
+                let rows: Vec<Vec<char>> = cells
+                    .chunks_exact(width as usize)
+                    .map(|row| row.iter().map(|&cell| PLANTS[cell as usize % 3]).collect())
+                    .collect();
+                if rows.is_empty() {
+                    return Ok(());
+                }
+                let input: String = rows
+                    .iter()
+                    .map(|row| row.iter().collect::<String>() + "\n")
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+
+                let plant = |(x, y): (i32, i32)| {
+                    let row = rows.get(usize::try_from(y).ok()?)?;
+                    row.get(usize::try_from(x).ok()?).copied()
+                };
+                // Every side of a plant facing another plant or the border has a fence, which ends
+                // a side of the region unless the next plant along it has the same fence
+                let fences = |(x, y): (i32, i32)| {
+                    [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                        .into_iter()
+                        .filter(move |&(dx, dy)| plant((x + dx, y + dy)) != plant((x, y)))
+                        .map(move |(dx, dy)| {
+                            let (along_x, along_y) = (x + dy, y + dx);
+                            plant((along_x, along_y)) != plant((x, y))
+                                || plant((along_x + dx, along_y + dy)) == plant((x, y))
+                        })
+                };
+                let (mut price, mut discounted_price) = (0, 0);
+                let mut seen = HashSet::new();
+
+                for start in (0..rows.len() as i32).flat_map(|y| (0..width).map(move |x| (x, y))) {
+                    if !seen.insert(start) {
+                        continue;
+                    }
+                    // Flood fills the region from its first plant
+                    let mut region = vec![start];
+                    let mut next = 0;
+                    while let Some(&(x, y)) = region.get(next) {
+                        for neighbour in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                            if plant(neighbour) == plant(start) && seen.insert(neighbour) {
+                                region.push(neighbour);
+                            }
+                        }
+                        next += 1;
+                    }
+
+                    let area = region.len() as u32;
+                    let fences = region.iter().flat_map(|&position| fences(position));
+                    price += area * fences.clone().count() as u32;
+                    discounted_price += area * fences.filter(|&ends_side| ends_side).count() as u32;
+                }
+
+                property::equal(problem.part_1(), price)?;
+                property::equal(problem.part_2(), Some(discounted_price))
+            },
+        );
+    }
+}
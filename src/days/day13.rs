@@ -0,0 +1,321 @@
+use crate::geometry::Vec2;
+use crate::parse::{self, Line, ParseError};
+use crate::solution::{ParamKind, Params, Solution};
+
+/// Offsets are read as `i64` but stored as `i128`, so that the products of the solution can't
+/// overflow
+#[derive(Debug)]
+struct Machine {
+    button_a: Vec2<i128>,
+    button_b: Vec2<i128>,
+    prize: Vec2<i128>,
+}
+
+impl Machine {
+    /// Using solution to the corresponding system of equations
+    fn prize_combination(&self) -> Option<(u128, u128)> {
+        let discriminant = self.button_b.x * self.button_a.y - self.button_a.x * self.button_b.y;
+
+        if discriminant == 0 {
+            // Divide by zero -> no solution
+            return None;
+        }
+
+        let numerator_a = self.button_b.x * self.prize.y - self.button_b.y * self.prize.x;
+        let numerator_b = self.button_a.x * self.prize.y - self.button_a.y * self.prize.x;
+
+        if numerator_a % discriminant != 0 || numerator_b % discriminant != 0 {
+            // Only accept integer solutions
+            return None;
+        }
+
+        // Buttons can't be pressed a negative number of times
+        Some((
+            u128::try_from(numerator_a / discriminant).ok()?,
+            u128::try_from(numerator_b / -discriminant).ok()?,
+        ))
+    }
+
+    fn correct_prize(&self, offset: i64) -> Self {
+        Self {
+            button_a: self.button_a,
+            button_b: self.button_b,
+            prize: self.prize + offset as i128,
+        }
+    }
+}
+
+/// Distance the prizes turn out to be further away in part 2, along both axes
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+/// Most times each button can be pressed in part 1
+const PRESS_LIMIT: u64 = 100;
+
+/// Tokens it costs to press button A and button B
+const COSTS: [u32; 2] = [3, 1];
+
+#[derive(Debug)]
+pub struct Problem {
+    machines: Vec<Machine>,
+    prize_offset: i64,
+    press_limit: u64,
+    costs: [u32; 2],
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            prize_offset: PRIZE_OFFSET,
+            press_limit: PRESS_LIMIT,
+            costs: COSTS,
+            machines: parse::sections(string)
+                .iter()
+                .map(|machine_lines| {
+                    let [a, b, prize] = machine_lines.as_slice() else {
+                        let first = machine_lines[0];
+                        return Err(first.error(
+                            first.text,
+                            format!(
+                                "expected a machine of 3 lines, found {} lines",
+                                machine_lines.len()
+                            ),
+                        ));
+                    };
+
+                    Ok(Machine {
+                        button_a: Self::vec2_from_line(a, "Button A: X+", ", Y+")?,
+                        button_b: Self::vec2_from_line(b, "Button B: X+", ", Y+")?,
+                        prize: Self::vec2_from_line(prize, "Prize: X=", ", Y=")?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn vec2_from_line(
+        line: &Line,
+        prefix: &str,
+        separator: &str,
+    ) -> Result<Vec2<i128>, ParseError> {
+        let (x, y) = line.split_once(line.strip_prefix(line.text, prefix)?, separator)?;
+
+        Ok(Vec2::new(
+            line.parse::<i64>(x, "an X offset")?.into(),
+            line.parse::<i64>(y, "a Y offset")?.into(),
+        ))
+    }
+
+    fn tokens(&self, (times_a, times_b): (u128, u128)) -> u128 {
+        times_a * u128::from(self.costs[0]) + times_b * u128::from(self.costs[1])
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 13;
+    const SAMPLE: &'static str = include_str!("../../samples/day13.txt");
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[
+        ("offset", ParamKind::Integer),
+        ("press_limit", ParamKind::Integer),
+        ("cost_a", ParamKind::Integer),
+        ("cost_b", ParamKind::Integer),
+    ];
+
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    /// The prize offset of part 2 can be overridden by the `offset` parameter, the presses per
+    /// button of part 1 by `press_limit`, and the cost of each button by `cost_a` and `cost_b`
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let mut problem = Self::from_string(input)?;
+        if let Some(offset) = params.get("offset") {
+            problem.prize_offset = offset;
+        }
+        if let Some(limit) = params.get_in("press_limit", 0..=u64::MAX)? {
+            problem.press_limit = limit;
+        }
+        for (cost, key) in problem.costs.iter_mut().zip(["cost_a", "cost_b"]) {
+            if let Some(value) = params.get_in(key, 0..=u32::MAX)? {
+                *cost = value;
+            }
+        }
+
+        Ok(problem)
+    }
+
+    fn part_1(&self) -> u128 {
+        self.machines
+            .iter()
+            .filter_map(|machine| machine.prize_combination())
+            .filter(|(times_a, times_b)| {
+                *times_a <= self.press_limit.into() && *times_b <= self.press_limit.into()
+            })
+            .map(|times| self.tokens(times))
+            .sum()
+    }
+
+    fn part_2(&self) -> Option<u128> {
+        Some(
+            self.machines
+                .iter()
+                .map(|machine| machine.correct_prize(self.prize_offset))
+                .filter_map(|machine| machine.prize_combination())
+                .map(|times| self.tokens(times))
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+
+    fn example(name: &str) -> String {
+        examples::input(13, name).unwrap()
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            0,
+            Problem::from_string(&example("simplified1"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(480, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+
+        // Only the first prize takes at most 80 presses per button, 80 of A and 40 of B
+        let params = "press_limit=80,cost_a=1".parse().unwrap();
+        assert_eq!(
+            120,
+            Problem::parse_with(Problem::SAMPLE, &params)
+                .unwrap()
+                .part_1()
+        );
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(875318608908),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+
+        // Without an offset, part 2 matches part 1 minus its limit of 100 presses per button
+        let params = "offset=0".parse().unwrap();
+        assert_eq!(
+            Some(480),
+            Problem::parse_with(Problem::SAMPLE, &params)
+                .unwrap()
+                .part_2()
+        );
+    }
+
+    #[test]
+    fn test_fractional_presses() {
+        // Would take half a press of A, the remainder being negative with a negative discriminant
+        let machine = "Button A: X+2, Y+2\nButton B: X+1, Y+3\nPrize: X=2, Y=4";
+        assert_eq!(0, Problem::from_string(machine).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        for params in ["cost_a=-1", "cost_b=4294967296", "press_limit=-1"] {
+            let error = Problem::parse_with(Problem::SAMPLE, &params.parse().unwrap()).unwrap_err();
+            assert_eq!(0, error.line, "{}", params);
+        }
+
+        // Neither large costs nor large offsets overflow
+        let params = "cost_a=4294967295,offset=9223372036854775807"
+            .parse()
+            .unwrap();
+        let problem = Problem::parse_with(Problem::SAMPLE, &params).unwrap();
+        assert_eq!(
+            80 * 4294967295 + 40 + 38 * 4294967295 + 86,
+            problem.part_1()
+        );
+        problem.part_2();
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Problem::from_string("Button A: X+94, Y+34\nButton B: X+22, Y+67")
+            .err()
+            .unwrap();
+        assert_eq!(
+            "expected a machine of 3 lines, found 2 lines",
+            error.message
+        );
+
+        let error = Problem::from_string("Button A: X+94, Y+34\nButton B: X+22\nPrize: X=1, Y=2")
+            .err()
+            .unwrap();
+        assert_eq!((2, 15), (error.line, error.column));
+        assert_eq!("expected ', Y+', found end of line", error.message);
+    }
+
+    #[test]
+    fn test_property_reference() {
+        property::check(
+            |rng| {
+                rng.vec(1..=4, |rng| {
+                    let buttons = rng.vec(4..=4, |rng| rng.range(1..=99));
+                    // A third of the prizes are reachable by construction, and another third
+                    // just miss, so that their solutions are fractional
+                    let prize = match rng.below(3) {
+                        0 | 1 => {
+                            let (a, b) = (rng.range(0..=120), rng.range(0..=120));
+                            vec![
+                                a * buttons[0] + b * buttons[2] + rng.range(0..=1),
+                                a * buttons[1] + b * buttons[3],
+                            ]
+                        }
+                        _ => rng.vec(2..=2, |rng| rng.range(0..=20000)),
+                    };
+                    (buttons, prize)
+                })
+            },
+            |machines| {
+                // The solution assumes the buttons move in different directions
+                let machines: Vec<&(Vec<i64>, Vec<i64>)> = machines
+                    .iter()
+                    .filter(|(b, p)| b.len() == 4 && p.len() == 2 && b[0] * b[3] != b[1] * b[2])
+                    .collect();
+                let input: Vec<String> = machines
+                    .iter()
+                    .map(|(b, p)| {
+                        format!(
+                            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                            b[0], b[1], b[2], b[3], p[0], p[1]
+                        )
+                    })
+                    .collect();
+                if input.is_empty() {
+                    return Ok(());
+                }
+                let problem = Problem::from_string(&input.join("\n\n")).unwrap();
+
+                // Try all combinations of at most 100 presses per button
+                let tokens = machines
+                    .iter()
+                    .filter_map(|(b, p)| {
+                        (0..=100)
+                            .flat_map(|a| (0..=100).map(move |b| (a, b)))
+                            .filter(|(a, c)| {
+                                a * b[0] + c * b[2] == p[0] && a * b[1] + c * b[3] == p[1]
+                            })
+                            .map(|(a, c)| a as u128 * 3 + c as u128)
+                            .min()
+                    })
+                    .sum();
+
+                property::equal(problem.part_1(), tokens)
+            },
+        );
+    }
+}
@@ -0,0 +1,545 @@
+use crate::animate::{Animate, Frame, Frames};
+use crate::answer::Answer;
+use crate::geometry::Vec2;
+use crate::grid::Grid;
+use crate::image::{Picture, Render, Rgb};
+use crate::parse::{self, ParseError};
+use crate::solution::{ParamKind, Params, Solution};
+use std::fmt::{Display, Formatter};
+
+type Vec2i = Vec2<i32>;
+
+#[derive(Debug, Copy, Clone)]
+struct Robot {
+    position: Vec2i,
+    velocity: Vec2i,
+}
+
+impl Robot {
+    fn elapse_time(&self, seconds: u32, area_dims: Vec2i) -> Self {
+        // Process boundary wrapping (teleports), in i64 as long moves overflow i32
+        let wrap = |position: i32, velocity: i32, size: i32| {
+            (position as i64 + velocity as i64 * seconds as i64).rem_euclid(size as i64) as i32
+        };
+
+        Self {
+            position: Vec2i::new(
+                wrap(self.position.x, self.velocity.x, area_dims.x),
+                wrap(self.position.y, self.velocity.y, area_dims.y),
+            ),
+            velocity: self.velocity,
+        }
+    }
+
+    /// Seconds until the robot is back in its starting position, which along each axis is the
+    /// size divided by the part of the velocity it shares with it
+    fn repeat_interval(&self, area_dims: Vec2i) -> u64 {
+        let axis = |velocity: i32, size: i32| {
+            size as u64 / gcd(size as u64, velocity.rem_euclid(size) as u64)
+        };
+
+        lcm(
+            axis(self.velocity.x, area_dims.x),
+            axis(self.velocity.y, area_dims.y),
+        )
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[derive(Debug, Clone)]
+struct Area {
+    dimensions: Vec2i,
+    robots: Vec<Robot>,
+}
+
+impl Area {
+    fn from_string(width: u32, height: u32, string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            dimensions: Vec2i::new(width as i32, height as i32),
+            robots: parse::lines(string)
+                .map(|line| {
+                    let (left, right) = line.split_once(line.text, " ")?;
+                    let (px, py) = line.split_once(line.strip_prefix(left, "p=")?, ",")?;
+                    let (vx, vy) = line.split_once(line.strip_prefix(right, "v=")?, ",")?;
+
+                    let inside =
+                        |field, size: u32, expected| match line.parse::<u32>(field, expected) {
+                            Ok(coordinate) if coordinate < size => Ok(coordinate as i32),
+                            _ => Err(line.expected(field, expected)),
+                        };
+
+                    Ok(Robot {
+                        position: Vec2i::new(
+                            inside(px, width, "an X position inside the area")?,
+                            inside(py, height, "a Y position inside the area")?,
+                        ),
+                        velocity: Vec2i::new(
+                            line.parse(vx, "an X velocity")?,
+                            line.parse(vy, "a Y velocity")?,
+                        ),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn elapse_time(&self, seconds: u32) -> Self {
+        Self {
+            dimensions: self.dimensions,
+            robots: self
+                .robots
+                .iter()
+                .map(|robot| robot.elapse_time(seconds, self.dimensions))
+                .collect(),
+        }
+    }
+
+    fn quadrants(&self) -> Vec<Self> {
+        let quadrant_dims = self.dimensions / 2;
+
+        (0..2)
+            .flat_map(|i| {
+                (0..2).map(move |j| Self {
+                    dimensions: quadrant_dims,
+                    robots: self
+                        .robots
+                        .iter()
+                        .filter(|robot| {
+                            robot.position.x >= (i * quadrant_dims.x) + i
+                                && robot.position.x < ((i + 1) * quadrant_dims.x) + i
+                                && robot.position.y >= (j * quadrant_dims.y) + j
+                                && robot.position.y < ((j + 1) * quadrant_dims.y) + j
+                        })
+                        .copied()
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Seconds until all robots are back in their starting positions at once
+    fn repeat_interval(&self) -> u64 {
+        self.robots.iter().fold(1, |interval, robot| {
+            lcm(interval, robot.repeat_interval(self.dimensions))
+        })
+    }
+
+    fn robot_count(&self) -> usize {
+        self.robots.len()
+    }
+
+    fn robot_at(&self, position: Vec2i) -> Option<&Robot> {
+        self.robots.iter().find(|robot| robot.position == position)
+    }
+
+    /// Leftover of failed attempt to assumed peak would be in the middle of the top row
+    fn _top_centered_robot(&self) -> Option<Robot> {
+        match self.robot_at(Vec2i::new(self.dimensions.x / 2, 0)) {
+            Some(&robot) => {
+                // return Some(robot);
+                if ((0..self.dimensions.x)
+                    .filter_map(|x| self.robot_at(Vec2i::new(x, 0)))
+                    .count())
+                    == 1
+                {
+                    return Some(robot);
+                }
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Searches for top robot of this shape:
+    ///
+    ///   #
+    ///  ###
+    /// #####
+    ///
+    /// Determined this shape after first searching for this shape (by guess) and then observing
+    /// those results to determine the filled shape above.
+    ///
+    ///   #
+    ///  # #
+    /// #   #
+    fn peak_robot(&self) -> Option<Robot> {
+        self.robots
+            .iter()
+            .find(|robot| {
+                self.robot_at(robot.position + Vec2i::new(-1, 1)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(1, 1)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(-2, 2)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(2, 2)).is_some()
+                    // These were added once I manually observed the tree using above criteria
+                    && self.robot_at(robot.position + Vec2i::new(0, 1)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(-1, 2)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(0, 2)).is_some()
+                    && self.robot_at(robot.position + Vec2i::new(1, 2)).is_some()
+            })
+            .cloned()
+    }
+}
+
+impl Area {
+    /// Map of the area with the tiles holding at least one robot marked by '#'
+    fn grid(&self) -> Grid<char> {
+        let (width, height) = (self.dimensions.x as usize, self.dimensions.y as usize);
+        let mut grid = Grid::new(width, height, vec!['.'; width * height]);
+        for robot in &self.robots {
+            grid[(robot.position.x as usize, robot.position.y as usize)] = '#';
+        }
+
+        grid
+    }
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid())
+    }
+}
+
+/// Seconds the robots move for in part 1
+const SECONDS: u32 = 100;
+
+/// Sizes the area can have, as positions are stored as `i32`
+const SIZES: std::ops::RangeInclusive<u32> = 1..=i32::MAX as u32;
+
+#[derive(Debug)]
+pub struct Problem {
+    area: Area,
+    seconds: u32,
+}
+
+impl Problem {
+    fn from_string(width: u32, height: u32, string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            area: Area::from_string(width, height, string)?,
+            seconds: SECONDS,
+        })
+    }
+
+    /// Seconds until the robots first form a Christmas tree, along with the area at that time.
+    /// Repeat interval = 10403, so manually seeking not really doable.
+    fn christmas_tree(&self) -> Option<(u64, Area)> {
+        let mut area = self.area.clone();
+        let seconds = (1..area.repeat_interval()).find(|_| {
+            area = area.elapse_time(1);
+            area.peak_robot().is_some()
+        })?;
+
+        Some((seconds, area))
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 14;
+    const SAMPLE: &'static str = include_str!("../../samples/day14.txt");
+    const SAMPLE_PARAMS: &'static [(&'static str, i64)] = &[("width", 11), ("height", 7)];
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[
+        ("width", ParamKind::Integer),
+        ("height", ParamKind::Integer),
+        ("seconds", ParamKind::Integer),
+    ];
+
+    type Part1 = usize;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    /// The area is 101 by 103 tiles, unless overridden by the positive `width` and `height`
+    /// parameters, and the seconds of part 1 can be overridden by the `seconds` parameter
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let width = params.get_in("width", SIZES)?.unwrap_or(101);
+        let height = params.get_in("height", SIZES)?.unwrap_or(103);
+        let mut problem = Self::from_string(width, height, input)?;
+        if let Some(seconds) = params.get_in("seconds", 0..=u32::MAX)? {
+            problem.seconds = seconds;
+        }
+
+        Ok(problem)
+    }
+
+    fn part_1(&self) -> usize {
+        self.area
+            .elapse_time(self.seconds)
+            .quadrants()
+            .iter()
+            .map(|quadrant| quadrant.robot_count())
+            .reduce(|acc, count| acc * count)
+            .unwrap()
+    }
+
+    /// The seconds until the Christmas tree forms, along with a drawing of it, or `None` if the
+    /// robots never form one
+    fn part_2(&self) -> Option<Answer> {
+        self.christmas_tree()
+            .map(|(seconds, area)| Answer::rendered(seconds, area))
+    }
+}
+
+impl Render for Problem {
+    /// The robots forming the Christmas tree, or in their starting positions if they never do
+    fn render(&self) -> Picture {
+        let area = self
+            .christmas_tree()
+            .map_or_else(|| self.area.clone(), |(_, area)| area);
+
+        Picture::Color(area.grid().map(|&tile| match tile {
+            '#' => Rgb::new(40, 200, 60),
+            _ => Rgb::new(10, 20, 40),
+        }))
+    }
+}
+
+impl Animate for Problem {
+    /// The robots moving second by second, until they are back in their starting positions
+    fn frames(self) -> Frames {
+        let mut area = self.area;
+        let seconds = area.repeat_interval();
+
+        Box::new((0..seconds).map(move |second| {
+            if second > 0 {
+                area = area.elapse_time(1);
+            }
+
+            Frame {
+                grid: area.grid(),
+                status: match area.peak_robot() {
+                    Some(_) => format!("Second {}: Christmas tree!", second),
+                    None => format!("Second {}", second),
+                },
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            12,
+            Problem::from_string(11, 7, Problem::SAMPLE)
+                .unwrap()
+                .part_1()
+        );
+
+        // After a single second, the quadrants hold 2, 1, 4 and 2 robots
+        let params = "width=11,height=7,seconds=1".parse().unwrap();
+        assert_eq!(
+            16,
+            Problem::parse_with(Problem::SAMPLE, &params)
+                .unwrap()
+                .part_1()
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let problem = Problem::from_string(11, 7, Problem::SAMPLE).unwrap();
+        let expected = problem.area.elapse_time(5).to_string();
+        let frames: Vec<_> = problem.frames().collect();
+
+        assert_eq!(77, frames.len());
+        assert_eq!("Second 5", frames[5].status);
+        assert_eq!(expected, frames[5].grid.to_string());
+    }
+
+    #[test]
+    fn test_render() {
+        // The sample never forms a tree, so the robots are drawn in their starting positions
+        let problem = Problem::from_string(11, 7, Problem::SAMPLE).unwrap();
+        let Picture::Color(colors) = problem.render() else {
+            panic!("expected a colour picture");
+        };
+
+        assert_eq!((11, 7), (colors.width(), colors.height()));
+        assert_eq!(Some(&Rgb::new(40, 200, 60)), colors.get(0, 4));
+        assert_eq!(Some(&Rgb::new(10, 20, 40)), colors.get(1, 0));
+    }
+
+    #[test]
+    fn test_no_christmas_tree() {
+        assert_eq!(
+            None,
+            Problem::from_string(11, 7, Problem::SAMPLE)
+                .unwrap()
+                .part_2()
+        );
+        assert_eq!(None, Problem::from_string(11, 7, "").unwrap().part_2());
+    }
+
+    #[test]
+    fn test_position_outside_area() {
+        assert_eq!(
+            ParseError::new(1, 5, "expected a Y position inside the area, found '7'"),
+            Problem::from_string(11, 7, "p=0,7 v=1,1").err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        for params in ["width=0", "height=-1", "width=2147483648", "seconds=-1"] {
+            let error = Problem::parse_with(Problem::SAMPLE, &params.parse().unwrap()).unwrap_err();
+            assert_eq!(0, error.line, "{}", params);
+        }
+
+        // Moves much longer than the area still wrap around without overflowing
+        let params = "width=11,height=7,seconds=4294967295".parse().unwrap();
+        let problem = Problem::parse_with("p=0,0 v=-1000,1000", &params).unwrap();
+        let position = problem.area.elapse_time(problem.seconds).robots[0].position;
+        assert_eq!(
+            Vec2i::new(
+                (-1000 * 4294967295i64).rem_euclid(11) as i32,
+                (1000 * 4294967295i64).rem_euclid(7) as i32
+            ),
+            position
+        );
+    }
+
+    #[test]
+    fn test_christmas_tree() {
+        // Robots moving right together, already forming a tree after the first second
+        let robots: Vec<String> = [
+            (3, 1),
+            (2, 2),
+            (3, 2),
+            (4, 2),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (4, 3),
+            (5, 3),
+        ]
+        .iter()
+        .map(|(x, y)| format!("p={},{} v=1,0", x, y))
+        .collect();
+        let answer = Problem::from_string(11, 7, &robots.join("\n"))
+            .unwrap()
+            .part_2()
+            .unwrap();
+
+        assert_eq!(answer, "1");
+        assert_eq!(
+            Some("...........\n....#......\n...###.....\n..#####....\n...........\n...........\n...........\n"),
+            answer.rendering()
+        );
+    }
+
+    #[test]
+    fn test_standing_robot() {
+        // Found by the property test, the search used to stop after the repeat interval of the
+        // first robot, which is a single second when it stands still
+        let robots = "p=0,6 v=0,0\np=3,1 v=1,0\np=2,2 v=1,0\np=3,2 v=1,0\np=4,2 v=1,0\n\
+                      p=1,3 v=1,0\np=2,3 v=1,0\np=3,3 v=1,0\np=4,3 v=1,0\np=5,3 v=1,0";
+        let problem = Problem::from_string(11, 7, robots).unwrap();
+
+        assert_eq!(11, problem.area.repeat_interval());
+        assert_eq!(problem.part_2().unwrap(), "1");
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Moves the robots one tile at a time, second by second
+        fn step(
+            positions: &mut [(i32, i32)],
+            velocities: &[(i32, i32)],
+            (width, height): (i32, i32),
+        ) {
+            for ((x, y), (vx, vy)) in positions.iter_mut().zip(velocities) {
+                for _ in 0..vx.abs() {
+                    *x = (*x + vx.signum() + width) % width;
+                }
+                for _ in 0..vy.abs() {
+                    *y = (*y + vy.signum() + height) % height;
+                }
+            }
+        }
+
+        property::check(
+            |rng| {
+                let dimensions = (rng.range(0..=4) as u8, rng.range(0..=4) as u8);
+                let robots = rng.vec(0..=40, |rng| {
+                    (
+                        rng.range(0..=99) as u32,
+                        rng.range(-3..=3) as i32,
+                        rng.range(-3..=3) as i32,
+                    )
+                });
+                (dimensions, rng.range(0..=10) as u8, robots)
+            },
+            |&((width, height), seconds, ref robots)| {
+                // Sizes are odd like the puzzle's, so that there are middle rows and columns
+                let (width, height) = (width as i32 * 2 + 1, height as i32 * 2 + 1);
+                let mut positions: Vec<(i32, i32)> = robots
+                    .iter()
+                    .map(|&(cell, _, _)| (cell as i32 % width, cell as i32 / width % height))
+                    .collect();
+                let velocities: Vec<(i32, i32)> =
+                    robots.iter().map(|&(_, vx, vy)| (vx, vy)).collect();
+
+                let input: Vec<String> = positions
+                    .iter()
+                    .zip(&velocities)
+                    .map(|((x, y), (vx, vy))| format!("p={},{} v={},{}", x, y, vx, vy))
+                    .collect();
+                let params = format!("width={},height={},seconds={}", width, height, seconds);
+                let problem =
+                    Problem::parse_with(&input.join("\n"), &params.parse().unwrap()).unwrap();
+
+                // Counts the robots in each quadrant, leaving out the middle row and column
+                let mut moved = positions.clone();
+                (0..seconds).for_each(|_| step(&mut moved, &velocities, (width, height)));
+                let mut quadrants = [0; 4];
+                for &(x, y) in &moved {
+                    if x != width / 2 && y != height / 2 {
+                        quadrants[(x > width / 2) as usize * 2 + (y > height / 2) as usize] += 1;
+                    }
+                }
+
+                // The robots are back in their starting positions after a multiple of both sizes
+                let repeat = (1..).find(|n| n % width == 0 && n % height == 0).unwrap();
+                let tree = (1..repeat).find(|_| {
+                    step(&mut positions, &velocities, (width, height));
+                    positions.iter().any(|&(x, y)| {
+                        [
+                            (0, 0),
+                            (-1, 1),
+                            (0, 1),
+                            (1, 1),
+                            (-2, 2),
+                            (-1, 2),
+                            (0, 2),
+                            (1, 2),
+                            (2, 2),
+                        ]
+                        .iter()
+                        .all(|(dx, dy)| positions.contains(&(x + dx, y + dy)))
+                    })
+                });
+
+                property::equal(problem.part_1(), quadrants.iter().product())?;
+                property::equal(
+                    problem.part_2().map(|answer| answer.to_string()),
+                    tree.map(|seconds| seconds.to_string()),
+                )
+            },
+        );
+    }
+}
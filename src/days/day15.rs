@@ -0,0 +1,394 @@
+use crate::animate::{Animate, Frame, Frames};
+use crate::geometry::{Direction, Vec2};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+type Vec2i = Vec2<i32>;
+
+#[derive(Debug)]
+struct Map {
+    dimensions: Vec2i,
+    boxes: HashSet<Vec2i>,
+    walls: HashSet<Vec2i>,
+    robot_pos: Vec2i,
+    directions: Vec<Vec2i>,
+}
+
+impl Map {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let (top, bottom) =
+            parse::two_sections(string, "a map and moves separated by a blank line")?;
+
+        // Positions and dimensions leave out the outer walls
+        let grid = parse::grid_lines(&top, |char| char)?;
+        let dimensions = Vec2i::new(grid.width() as i32 - 2, grid.height() as i32 - 2);
+        let mut robot_pos = None;
+        for (y, line) in top.iter().enumerate() {
+            for (x, (offset, char)) in line.text.char_indices().enumerate() {
+                let at = &line.text[offset..offset + char.len_utf8()];
+                let outer = x == 0 || y == 0 || x + 1 == grid.width() || y + 1 == grid.height();
+
+                match char {
+                    '#' => {}
+                    _ if outer => return Err(line.expected(at, "a wall '#' around the map")),
+                    '.' | 'O' => {}
+                    '@' if robot_pos.is_none() => {
+                        robot_pos = Some(Vec2i::new(x as i32 - 1, y as i32 - 1));
+                    }
+                    '@' => return Err(line.error(at, "expected a single robot '@', found another")),
+                    _ => {
+                        return Err(line.expected(
+                            at,
+                            "a wall '#', a box 'O', the robot '@' or an empty tile '.'",
+                        ))
+                    }
+                }
+            }
+        }
+
+        let objects = |char| {
+            grid.find_all(&char)
+                .map(|(x, y)| Vec2i::new(x as i32 - 1, y as i32 - 1))
+                .filter(|pos| {
+                    (0..dimensions.x).contains(&pos.x) && (0..dimensions.y).contains(&pos.y)
+                })
+                .collect::<HashSet<Vec2i>>()
+        };
+
+        Ok(Self {
+            dimensions,
+            boxes: objects('O'),
+            walls: objects('#'),
+            robot_pos: robot_pos.ok_or_else(|| ParseError::missing(string, "a robot '@'"))?,
+            directions: bottom
+                .iter()
+                .flat_map(|line| {
+                    line.text.char_indices().map(|(offset, char)| {
+                        Direction::from_arrow(char)
+                            .map(Direction::delta)
+                            .ok_or_else(|| {
+                                line.expected(
+                                    &line.text[offset..offset + char.len_utf8()],
+                                    "a move '^', '>', 'v' or '<'",
+                                )
+                            })
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn is_accessible(&self, pos: Vec2i) -> bool {
+        pos.x >= 0
+            && pos.x < self.dimensions.x
+            && pos.y >= 0
+            && pos.y < self.dimensions.y
+            && !self.walls.contains(&pos)
+    }
+
+    /// Moves the robot a single step in the given direction, pushing along any boxes in front of
+    /// it, unless a wall is in the way
+    fn step(&self, boxes: &mut HashSet<Vec2i>, robot_pos: &mut Vec2i, direction: Vec2i) {
+        let mut boxes_to_move = vec![];
+        for i in 1.. {
+            let position = *robot_pos + (direction * i);
+
+            if !self.is_accessible(position) {
+                // Out of bounds, can't move in this direction
+                trace::event!(Trace, "blocked", robot = robot_pos, direction = direction);
+                return;
+            }
+
+            if boxes.contains(&position) {
+                // Box, so queue its movement and check next position
+                boxes_to_move.push(position);
+                continue;
+            }
+
+            // Free spot found, so break the loop and perform the queued movements
+            break;
+        }
+
+        // Perform movements
+        for &box_pos in boxes_to_move.iter().rev() {
+            boxes.remove(&box_pos); // remove from old space
+            boxes.insert(box_pos + direction); // insert into new pos
+        }
+
+        // Once the boxes are moved, we can move the robot
+        *robot_pos += direction;
+        trace::event!(
+            Trace,
+            "moved",
+            robot = robot_pos,
+            direction = direction,
+            pushed = boxes_to_move.len()
+        );
+    }
+
+    fn move_robot(&self) -> Self {
+        let mut boxes = self.boxes.clone();
+        let mut robot_pos = self.robot_pos;
+
+        for &direction in self.directions.iter() {
+            self.step(&mut boxes, &mut robot_pos, direction);
+        }
+        trace::event!(
+            Debug,
+            "moved robot",
+            moves = self.directions.len(),
+            robot = robot_pos,
+            gps_sum = Self::gps_sum(&boxes)
+        );
+
+        Self {
+            dimensions: self.dimensions,
+            walls: self.walls.clone(),
+            boxes,
+            robot_pos,
+            directions: self.directions.clone(),
+        }
+    }
+
+    /// Map including the outer walls, which are left out of the dimensions
+    fn grid(&self, boxes: &HashSet<Vec2i>, robot_pos: Vec2i) -> Grid<char> {
+        let (width, height) = (
+            self.dimensions.x as usize + 2,
+            self.dimensions.y as usize + 2,
+        );
+        let mut grid = Grid::new(width, height, vec!['#'; width * height]);
+        let cell = |pos: Vec2i| (pos.x as usize + 1, pos.y as usize + 1);
+
+        for x in 0..self.dimensions.x {
+            for y in 0..self.dimensions.y {
+                let pos = Vec2i::new(x, y);
+                grid[cell(pos)] = match (self.walls.contains(&pos), boxes.contains(&pos)) {
+                    (true, _) => '#',
+                    (_, true) => 'O',
+                    _ => '.',
+                };
+            }
+        }
+        grid[cell(robot_pos)] = '@';
+
+        grid
+    }
+
+    fn box_gps_sum(&self) -> u32 {
+        Self::gps_sum(&self.boxes)
+    }
+
+    fn gps_sum(boxes: &HashSet<Vec2i>) -> u32 {
+        boxes
+            .iter()
+            .map(|box_pos| (box_pos.x + 1 + (box_pos.y + 1) * 100) as u32)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    map: Map,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::from_string(string)?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 15;
+    const SAMPLE: &'static str = include_str!("../../samples/day15.txt");
+
+    type Part1 = u32;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> u32 {
+        self.map.move_robot().box_gps_sum()
+    }
+
+    fn part_2(&self) -> Option<Infallible> {
+        None
+    }
+}
+
+impl Animate for Problem {
+    /// The robot's moves, one frame per attempted move
+    fn frames(self) -> Frames {
+        let map = self.map;
+        let mut boxes = map.boxes.clone();
+        let mut robot_pos = map.robot_pos;
+
+        Box::new((0..=map.directions.len()).map(move |i| {
+            let status = match map.directions.get(i.wrapping_sub(1)) {
+                Some(&direction) => {
+                    map.step(&mut boxes, &mut robot_pos, direction);
+                    format!(
+                        "Move {} of {} ({}), GPS sum {}",
+                        i,
+                        map.directions.len(),
+                        Direction::from_delta(direction)
+                            .and_then(Direction::arrow)
+                            .unwrap_or('?'),
+                        Map::gps_sum(&boxes)
+                    )
+                }
+                None => format!("Start, GPS sum {}", Map::gps_sum(&boxes)),
+            };
+
+            Frame {
+                grid: map.grid(&boxes, robot_pos),
+                status,
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+
+    fn example(name: &str) -> String {
+        examples::input(15, name).unwrap()
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            2028,
+            Problem::from_string(&example("simplified"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(
+            10092,
+            Problem::from_string(Problem::SAMPLE).unwrap().part_1()
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames: Vec<_> = Problem::from_string(&example("simplified"))
+            .unwrap()
+            .frames()
+            .collect();
+
+        assert_eq!(16, frames.len());
+        assert_eq!(
+            example("simplified").split("\n\n").next().unwrap(),
+            frames[0].grid.to_string().trim_end()
+        );
+        assert_eq!("Move 15 of 15 (<), GPS sum 2028", frames[15].status);
+        assert_eq!('@', frames[15].grid[(4, 4)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Problem::from_string("####\n#@O#\n####\n\n<>x^")
+            .err()
+            .unwrap();
+        assert_eq!((5, 3), (error.line, error.column));
+        assert_eq!(
+            "expected a move '^', '>', 'v' or '<', found 'x'",
+            error.message
+        );
+
+        assert!(Problem::from_string("####\n#.O#\n####\n\n<>").is_err());
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(
+            ParseError::new(
+                2,
+                3,
+                "expected a wall '#', a box 'O', the robot '@' or an empty tile '.', found 'x'"
+            ),
+            Problem::from_string("#####\n#@x.#\n#####\n\n<").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(2, 4, "expected a single robot '@', found another"),
+            Problem::from_string("#####\n#@.@#\n#####\n\n<").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(2, 5, "expected a wall '#' around the map, found '.'"),
+            Problem::from_string("#####\n#@...\n#####\n\n<").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_property_reference() {
+        const TILES: [char; 4] = ['.', '.', 'O', '#'];
+        const MOVES: [(char, (isize, isize)); 4] =
+            [('^', (0, -1)), ('>', (1, 0)), ('v', (0, 1)), ('<', (-1, 0))];
+
+        property::check(
+            |rng| {
+                let (width, robot) = (rng.range(1..=6) as u8, rng.below(36) as u8);
+                let cells = rng.vec(1..=36, |rng| rng.below(TILES.len()) as u8);
+                let moves = rng.vec(1..=30, |rng| rng.below(MOVES.len()) as u8);
+                ((width, robot), cells, moves)
+            },
+            |((width, robot), cells, moves)| {
+                // The map is surrounded by walls, with the robot on one of the inner tiles
+                let width = (*width as usize).max(1);
+                let height = cells.len() / width;
+                if height == 0 || moves.is_empty() {
+                    return Ok(());
+                }
+                let mut map = vec![vec!['#'; width + 2]; height + 2];
+                for (i, &cell) in cells[..width * height].iter().enumerate() {
+                    map[i / width + 1][i % width + 1] = TILES[cell as usize % TILES.len()];
+                }
+                let robot = *robot as usize % (width * height);
+                let (mut x, mut y) = (robot % width + 1, robot / width + 1);
+                map[y][x] = '@';
+
+                let rows: Vec<String> = map.iter().map(|row| row.iter().collect()).collect();
+                let arrows: String = moves.iter().map(|&m| MOVES[m as usize % 4].0).collect();
+                let input = format!("{}\n\n{}", rows.join("\n"), arrows);
+                let problem = Problem::from_string(&input).unwrap();
+
+                // Looks past the boxes in front of the robot, then moves the first box to the end
+                // of the line when there is room there
+                for &m in moves {
+                    let (dx, dy) = MOVES[m as usize % 4].1;
+                    let next = |(x, y): (usize, usize)| {
+                        ((x as isize + dx) as usize, (y as isize + dy) as usize)
+                    };
+                    let mut end = next((x, y));
+                    while map[end.1][end.0] == 'O' {
+                        end = next(end);
+                    }
+                    if map[end.1][end.0] == '.' {
+                        let (to_x, to_y) = next((x, y));
+                        map[end.1][end.0] = map[to_y][to_x];
+                        map[to_y][to_x] = '@';
+                        map[y][x] = '.';
+                        (x, y) = (to_x, to_y);
+                    }
+                }
+                let gps_sum = (0..height + 2)
+                    .flat_map(|y| (0..width + 2).map(move |x| (x, y)))
+                    .filter(|&(x, y)| map[y][x] == 'O')
+                    .map(|(x, y)| (100 * y + x) as u32)
+                    .sum();
+
+                property::equal(problem.part_1(), gps_sum)
+            },
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::geometry::{Direction, Vec2};
+use crate::grid::Grid;
+use crate::image::{Picture, Render, Rgb};
+use crate::parse::{self, ParseError};
+use crate::search::{self, Search};
+use crate::solution::{ParamKind, Params, Solution};
+use crate::trace;
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+type Vec2i = Vec2<i32>;
+
+/// Points it costs to move forward and to turn 90 degrees
+const COSTS: [u32; 2] = [1, 1000];
+
+#[derive(Debug)]
+struct Map {
+    size: Vec2i,
+    walls: HashSet<Vec2i>,
+    start: Vec2i,
+    finish: Vec2i,
+    costs: [u32; 2],
+}
+
+impl Map {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let grid = parse::grid(string, |char| char)?;
+        let position = |char, expected| match grid.find(&char) {
+            Some((x, y)) => Ok(Vec2i::new(x as i32, y as i32)),
+            None => Err(ParseError::missing(string, expected)),
+        };
+
+        let map = Self {
+            size: Vec2i::new(grid.width() as i32, grid.height() as i32),
+            walls: grid
+                .find_all(&'#')
+                .map(|(x, y)| Vec2i::new(x as i32, y as i32))
+                .collect(),
+            start: position('S', "a start tile 'S'")?,
+            finish: position('E', "an end tile 'E'")?,
+            costs: COSTS,
+        };
+
+        // Rule out mazes without a solution up front, so that solving them can't fail
+        let reachable = search::bfs(
+            map.start,
+            |&position| {
+                Direction::CARDINAL
+                    .iter()
+                    .map(move |direction| position + direction.delta())
+                    .filter(|&next| map.is_open(next))
+                    .collect::<Vec<_>>()
+            },
+            |&position| position == map.finish,
+        );
+        match reachable.cost() {
+            Some(_) => Ok(map),
+            None => Err(ParseError::new(
+                map.finish.y as usize + 1,
+                map.finish.x as usize + 1,
+                "the end tile 'E' can't be reached from the start tile 'S'",
+            )),
+        }
+    }
+
+    /// Whether the position lies within the maze and is not a wall
+    fn is_open(&self, position: Vec2i) -> bool {
+        (0..self.size.x).contains(&position.x)
+            && (0..self.size.y).contains(&position.y)
+            && !self.walls.contains(&position)
+    }
+
+    fn points_from_to(&self) -> Option<u64> {
+        let points = self.cheapest_paths().cost();
+        trace::event!(Debug, "searched maze", points = ?points);
+
+        points
+    }
+
+    /// Cheapest ways through the maze, where each state is a position along with the direction
+    /// faced. Moving forward costs 1 point and turning 90 degrees costs 1000 points, unless the
+    /// costs were overridden. Points add up in `u64`, so that large costs can't overflow.
+    fn cheapest_paths(&self) -> Search<(Vec2i, Direction), u64> {
+        let [move_cost, turn_cost] = self.costs.map(u64::from);
+
+        search::dijkstra(
+            (self.start, Direction::East),
+            |&(position, direction)| {
+                trace::event!(Trace, "expanding", position = position, direction = ?direction);
+                let forward = position + direction.delta();
+
+                [
+                    self.is_open(forward)
+                        .then_some(((forward, direction), move_cost)),
+                    Some(((position, direction.turn_left()), turn_cost)),
+                    Some(((position, direction.turn_right()), turn_cost)),
+                ]
+                .into_iter()
+                .flatten()
+            },
+            |&(position, _)| position == self.finish,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    map: Map,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::from_string(string)?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 16;
+    const SAMPLE: &'static str = include_str!("../../samples/day16.txt");
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[
+        ("move_cost", ParamKind::Integer),
+        ("turn_cost", ParamKind::Integer),
+    ];
+
+    type Part1 = u64;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    /// The points of moving and turning can be overridden by the `move_cost` and `turn_cost`
+    /// parameters
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let mut problem = Self::from_string(input)?;
+        for (cost, key) in problem.map.costs.iter_mut().zip(["move_cost", "turn_cost"]) {
+            if let Some(value) = params.get_in(key, 0..=u32::MAX)? {
+                *cost = value;
+            }
+        }
+
+        Ok(problem)
+    }
+
+    fn part_1(&self) -> u64 {
+        self.map.points_from_to().expect("No solution!")
+    }
+
+    fn part_2(&self) -> Option<Infallible> {
+        None
+    }
+}
+
+impl Render for Problem {
+    /// The maze with one of the cheapest paths from start to end
+    fn render(&self) -> Picture {
+        let map = &self.map;
+        let (width, height) = (map.size.x as usize, map.size.y as usize);
+        let mut colors = Grid::new(width, height, vec![Rgb::WHITE; width * height]);
+        let cell = |position: Vec2i| (position.x as usize, position.y as usize);
+
+        for &wall in &map.walls {
+            colors[cell(wall)] = Rgb::gray(60);
+        }
+        for (position, _) in map.cheapest_paths().path().unwrap_or_default() {
+            colors[cell(position)] = Rgb::new(220, 40, 40);
+        }
+        colors[cell(map.start)] = Rgb::new(40, 160, 40);
+        colors[cell(map.finish)] = Rgb::new(40, 80, 220);
+
+        Picture::Color(colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+    use std::collections::HashMap;
+
+    fn example(name: &str) -> String {
+        examples::input(16, name).unwrap()
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(
+            7036,
+            Problem::from_string(&example("simplified"))
+                .unwrap()
+                .part_1()
+        );
+        assert_eq!(
+            11048,
+            Problem::from_string(Problem::SAMPLE).unwrap().part_1()
+        );
+    }
+
+    #[test]
+    fn test_cost_params() {
+        // Without turning costs, the points are the length of the shortest path
+        let params = "turn_cost=0".parse().unwrap();
+        let problem = Problem::parse_with(&example("simplified"), &params).unwrap();
+        assert_eq!(28, problem.part_1());
+
+        // With cheap turns, the shortest path is taken along with its 10 turns
+        let params = "move_cost=2,turn_cost=1".parse().unwrap();
+        let problem = Problem::parse_with(&example("simplified"), &params).unwrap();
+        assert_eq!(66, problem.part_1());
+
+        let params = "move_cost=4294967295,turn_cost=4294967295".parse().unwrap();
+        let problem = Problem::parse_with(&example("simplified"), &params).unwrap();
+        assert!(problem.part_1() > u32::MAX as u64);
+
+        for params in ["move_cost=-1", "turn_cost=4294967296"] {
+            let error =
+                Problem::parse_with(&example("simplified"), &params.parse().unwrap()).unwrap_err();
+            assert_eq!(0, error.line, "{}", params);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let problem = Problem::from_string("#####\n#..E#\n#S###\n#####").unwrap();
+        let mut expected = b"P6\n5 4\n255\n".to_vec();
+        for row in ["WWWWW", "WPPEW", "WSWWW", "WWWWW"] {
+            expected.extend(row.chars().flat_map(|pixel| match pixel {
+                'W' => [60, 60, 60],
+                'P' => [220, 40, 40],
+                'S' => [40, 160, 40],
+                _ => [40, 80, 220],
+            }));
+        }
+
+        assert_eq!(expected, crate::image::ppm(&problem.render(), 1));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Problem::from_string("#####\n#S..#\n#####").err().unwrap();
+
+        assert_eq!(
+            "expected an end tile 'E', found end of input",
+            error.message
+        );
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        assert_eq!(
+            ParseError::new(
+                1,
+                4,
+                "the end tile 'E' can't be reached from the start tile 'S'"
+            ),
+            Problem::from_string(".S#E\n..#.").err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // East, south, west and north, so that turning adds or removes one
+        const DELTAS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+        property::check(
+            |rng| {
+                let (width, start, end) = (rng.range(1..=6) as u8, rng.below(36), rng.below(36));
+                let cells = rng.vec(1..=36, |rng| (rng.below(3) == 0) as u8);
+                let costs = (rng.range(0..=5) as u32, rng.range(0..=2000) as u32);
+                ((width, start as u8, end as u8), cells, costs)
+            },
+            |&((width, start, end), ref cells, (move_cost, turn_cost))| {
+                let width = (width as usize).max(1);
+                let height = cells.len() / width;
+                if height == 0 {
+                    return Ok(());
+                }
+                let mut tiles: Vec<char> = cells[..width * height]
+                    .iter()
+                    .map(|&wall| if wall == 1 { '#' } else { '.' })
+                    .collect();
+                let (start, end) = (start as usize % tiles.len(), end as usize % tiles.len());
+                if start == end {
+                    return Ok(());
+                }
+                tiles[start] = 'S';
+                tiles[end] = 'E';
+                let rows: Vec<String> = tiles
+                    .chunks(width)
+                    .map(|row| row.iter().collect())
+                    .collect();
+                let params = format!("move_cost={},turn_cost={}", move_cost, turn_cost);
+                let problem = Problem::parse_with(&rows.join("\n"), &params.parse().unwrap());
+
+                // Relaxes the points of every state until none of them improves
+                let position = |index: usize| ((index % width) as i32, (index / width) as i32);
+                let open = |(x, y): (i32, i32)| {
+                    (0..width as i32).contains(&x)
+                        && (0..height as i32).contains(&y)
+                        && tiles[y as usize * width + x as usize] != '#'
+                };
+                let mut points = HashMap::from([((position(start), 0), 0u64)]);
+                let mut improved = true;
+                while improved {
+                    improved = false;
+                    for (((x, y), facing), current) in points.clone() {
+                        let (dx, dy) = DELTAS[facing];
+                        let forward = (x + dx, y + dy);
+                        let moves = [
+                            (open(forward), (forward, facing), move_cost),
+                            (true, ((x, y), (facing + 1) % 4), turn_cost),
+                            (true, ((x, y), (facing + 3) % 4), turn_cost),
+                        ];
+                        for (_, state, cost) in moves.into_iter().filter(|&(allowed, ..)| allowed) {
+                            let next = current + cost as u64;
+                            if points.get(&state).is_none_or(|&known| next < known) {
+                                points.insert(state, next);
+                                improved = true;
+                            }
+                        }
+                    }
+                }
+                let finish = (0..4)
+                    .filter_map(|facing| points.get(&(position(end), facing)))
+                    .min();
+
+                match (problem, finish) {
+                    (Ok(problem), Some(&finish)) => property::equal(problem.part_1(), finish),
+                    (Err(_), None) => Ok(()),
+                    (problem, finish) => Err(format!(
+                        "expected {:?}, got {:?}",
+                        finish,
+                        problem.map(|problem| problem.part_1())
+                    )),
+                }
+            },
+        );
+    }
+}
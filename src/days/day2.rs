@@ -0,0 +1,262 @@
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+#[derive(Debug, Eq, PartialEq)]
+struct Report {
+    levels: Vec<i32>,
+}
+
+impl Report {
+    fn new(levels: Vec<i32>) -> Report {
+        Self { levels }
+    }
+
+    /// Returns a sign number indicating the average gradient of the report levels
+    /// (-1 = descending, +1 = ascending, 0 = no gradient)
+    fn gradient_sign(&self) -> i32 {
+        self.levels
+            .windows(2)
+            .map(|window| (window[1] - window[0]).signum())
+            .sum::<i32>()
+            .signum()
+    }
+
+    /// Returns the index of the left operand of the first encountered unsafe difference
+    fn unsafe_level_index(&self) -> Option<usize> {
+        let gradient_sign = self.gradient_sign();
+
+        // Return index of left operand if the absolute difference with the right operand exceeds
+        // range 1-3 or when the sign of the difference does not match the sign of the average
+        // gradient of the report.
+        self.levels
+            .windows(2)
+            .enumerate()
+            .find(|(_, window)| {
+                (window[1] - window[0]).signum() != gradient_sign
+                    || !(1..=3).contains(&window[1].abs_diff(window[0]))
+            })
+            .map(|(index, _)| index)
+    }
+
+    fn is_safe(&self) -> bool {
+        self.unsafe_level_index().is_none()
+    }
+
+    fn is_safe_tolerated(&self) -> bool {
+        match self.unsafe_level_index() {
+            None => true,
+            Some(index) => {
+                // Test safety once more without the left level, followed by another test without the right level
+                self.without_level(index).unsafe_level_index().is_none()
+                    || self.without_level(index + 1).unsafe_level_index().is_none()
+            }
+        }
+    }
+
+    fn without_level(&self, idx: usize) -> Self {
+        let mut levels = self.levels.clone();
+        levels.remove(idx);
+
+        Self { levels }
+    }
+}
+
+pub struct Problem {
+    reports: Vec<Report>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            reports: parse::lines(string)
+                .map(|line| {
+                    Ok(Report::new(
+                        line.text
+                            .split_whitespace()
+                            .map(|value| line.parse::<i32>(value, "a level"))
+                            .collect::<Result<_, _>>()?,
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 2;
+    const SAMPLE: &'static str = include_str!("../../samples/day2.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.reports
+            .iter()
+            .filter(|report| report.is_safe())
+            .count()
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        Some(
+            self.reports
+                .iter()
+                .filter(|report| report.is_safe_tolerated())
+                .count(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(2, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(4),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+    }
+
+    #[test]
+    fn test_unsafe_level_index() {
+        // Sample reports
+        assert_eq!(None, Report::new(vec![7, 6, 4, 2, 1]).unsafe_level_index());
+        assert_eq!(
+            Some(1),
+            Report::new(vec![1, 2, 7, 8, 9]).unsafe_level_index()
+        );
+        assert_eq!(
+            Some(2),
+            Report::new(vec![9, 7, 6, 2, 1]).unsafe_level_index()
+        );
+        assert_eq!(
+            Some(1),
+            Report::new(vec![1, 3, 2, 4, 5]).unsafe_level_index()
+        );
+        assert_eq!(
+            Some(2),
+            Report::new(vec![8, 6, 4, 4, 1]).unsafe_level_index()
+        );
+        assert_eq!(None, Report::new(vec![1, 3, 6, 7, 9]).unsafe_level_index());
+
+        // Other reports
+        assert_eq!(None, Report::new(vec![1, 4, 7, 8, 10]).unsafe_level_index());
+        assert_eq!(
+            Some(0),
+            Report::new(vec![1, 1, 7, 8, 10]).unsafe_level_index()
+        );
+        assert_eq!(
+            Some(1),
+            Report::new(vec![1, 4, 4, 8, 10]).unsafe_level_index()
+        );
+    }
+
+    #[test]
+    fn test_is_safe_tolerated() {
+        // Sample reports
+        assert!(Report::new(vec![7, 6, 4, 2, 1]).is_safe_tolerated());
+        assert!(!Report::new(vec![1, 2, 7, 8, 9]).is_safe_tolerated());
+        assert!(!Report::new(vec![9, 7, 6, 2, 1]).is_safe_tolerated());
+        assert!(Report::new(vec![1, 3, 2, 4, 5]).is_safe_tolerated());
+        assert!(Report::new(vec![8, 6, 4, 4, 1]).is_safe_tolerated());
+        assert!(Report::new(vec![1, 3, 6, 7, 9]).is_safe_tolerated());
+
+        // Other
+        assert!(Report::new(vec![1, 1, 2, 3, 4]).is_safe_tolerated());
+        assert!(!Report::new(vec![1, 1, 1, 3, 4]).is_safe_tolerated());
+        assert!(Report::new(vec![4, 4, 3, 2, 1]).is_safe_tolerated());
+        assert!(!Report::new(vec![4, 4, 4, 2, 1]).is_safe_tolerated());
+        assert!(Report::new(vec![2, 3, 3, 6, 7]).is_safe_tolerated());
+        assert!(!Report::new(vec![3, 3, 3, 6, 7]).is_safe_tolerated());
+
+        // This case was causing the faulty first attempts (gradient was based on first two levels)
+        assert!(Report::new(vec![3, 1, 2, 3]).is_safe_tolerated());
+
+        // Case where comparing first with last to determine gradient would fail
+        assert!(Report::new(vec![5, 2, 3, 4]).is_safe_tolerated());
+
+        // From actual input
+        assert!(Report::new(vec![47, 49, 50, 52, 53, 54, 57, 59]).is_safe_tolerated());
+    }
+
+    #[test]
+    fn test_without_level() {
+        assert_eq!(
+            Report::new(vec![2, 3]),
+            Report::new(vec![1, 2, 3]).without_level(0),
+        );
+        assert_eq!(
+            Report::new(vec![1, 3]),
+            Report::new(vec![1, 2, 3]).without_level(1),
+        );
+        assert_eq!(
+            Report::new(vec![1, 2]),
+            Report::new(vec![1, 2, 3]).without_level(2),
+        );
+    }
+
+    #[test]
+    fn test_property_reference() {
+        fn is_safe(levels: &[i32]) -> bool {
+            let differences: Vec<i32> = levels.windows(2).map(|w| w[1] - w[0]).collect();
+
+            differences.iter().all(|d| (1..=3).contains(d))
+                || differences.iter().all(|d| (-3..=-1).contains(d))
+        }
+
+        property::check(
+            |rng| {
+                rng.vec(1..=10, |rng| {
+                    // Mostly gradual reports, since random ones are hardly ever safe
+                    let step = if rng.bool() { 1 } else { -1 };
+                    let mut level = rng.range(1..=20) as i32;
+                    rng.vec(2..=8, |rng| {
+                        level += step * rng.range(-1..=4) as i32;
+                        level
+                    })
+                })
+            },
+            |reports| {
+                let reports: Vec<&Vec<i32>> = reports.iter().filter(|r| r.len() >= 2).collect();
+                let input: String = reports
+                    .iter()
+                    .map(|levels| {
+                        let levels: Vec<String> = levels.iter().map(i32::to_string).collect();
+                        levels.join(" ") + "\n"
+                    })
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+
+                // Try removing every single level
+                let tolerated = reports
+                    .iter()
+                    .filter(|levels| {
+                        is_safe(levels)
+                            || (0..levels.len()).any(|i| {
+                                let mut without = levels.to_vec();
+                                without.remove(i);
+                                is_safe(&without)
+                            })
+                    })
+                    .count();
+
+                property::equal(
+                    problem.part_1(),
+                    reports.iter().filter(|levels| is_safe(levels)).count(),
+                )?;
+                property::equal(problem.part_2(), Some(tolerated))
+            },
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::parse::ParseError;
+use crate::solution::Solution;
+
+pub struct Problem {
+    instructions: String,
+}
+
+impl Problem {
+    /// Any input is accepted, corrupted instructions are simply ignored
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            instructions: string.to_string(),
+        })
+    }
+
+    /// Operands are read as `u64` and multiplied as `u128`, so that their products can't overflow
+    fn process(&self, instructions: &str) -> u128 {
+        instructions
+            .match_indices("mul(")
+            .filter_map(|(start, _)| {
+                let offset = start + 4; // length of "mul("
+                instructions[offset..(offset + instructions[offset..].find(")")?)].split_once(',')
+            })
+            .filter_map(|(left_operand, right_operand)| {
+                let operand =
+                    |operand: &str| match operand.chars().all(|char| char.is_ascii_digit()) {
+                        true => operand.parse::<u64>().ok(),
+                        false => None,
+                    };
+
+                Some(u128::from(operand(left_operand)?) * u128::from(operand(right_operand)?))
+            })
+            .sum()
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 3;
+    const SAMPLE: &'static str = include_str!("../../samples/day3.txt");
+
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> u128 {
+        self.process(&self.instructions)
+    }
+
+    fn part_2(&self) -> Option<u128> {
+        Some(
+            self.instructions
+                .split("don't()")
+                .enumerate()
+                // Keep the first part (assume do()) and the right of do() in the others, each
+                // processed on its own so that the removed sections can't glue instructions
+                .map(|(i, part)| match (i, part.split_once("do()")) {
+                    (0, _) => part,
+                    (_, Some((_, right))) => right,
+                    (_, None) => "",
+                })
+                .map(|part| self.process(part))
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(161, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(48),
+            Problem::from_string(&examples::input(3, "part2").unwrap())
+                .unwrap()
+                .part_2()
+        );
+    }
+
+    #[test]
+    fn test_invalid_operands() {
+        // Found by fuzzing, empty operands used to fail to parse and large ones to overflow
+        assert_eq!(
+            8 + 1234 * 2 + 3 * 4 + 18446744073709551615 * 2,
+            Problem::from_string("mul(2,4)mul(,5)mul(1234,2)mul(3,4)mul(18446744073709551615,2)")
+                .unwrap()
+                .part_1()
+        );
+    }
+
+    #[test]
+    fn test_enabled_sections() {
+        // Found by the property test, a do() used to drop what came before the first don't(),
+        // and removing a disabled section used to glue the remaining text into an instruction
+        let part_2 = |input| Problem::from_string(input).unwrap().part_2();
+
+        assert_eq!(Some(1), part_2("mul(1,1)do()"));
+        assert_eq!(Some(0), part_2("mul(don't()do()2,3)"));
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Scans every position for the instructions, enabling and disabling multiplications
+        fn reference(input: &str) -> (u128, u128) {
+            fn number(string: &str) -> Option<(u128, &str)> {
+                let len = string
+                    .find(|char: char| !char.is_ascii_digit())
+                    .unwrap_or(string.len());
+                (len > 0).then(|| (string[..len].parse().unwrap(), &string[len..]))
+            }
+            fn product(arguments: &str) -> Option<u128> {
+                let (left, rest) = number(arguments)?;
+                let (right, rest) = number(rest.strip_prefix(',')?)?;
+                rest.starts_with(')').then_some(left * right)
+            }
+
+            let (mut all, mut enabled_only, mut enabled) = (0, 0, true);
+            for start in 0..input.len() {
+                let rest = &input[start..];
+                if rest.starts_with("do()") {
+                    enabled = true;
+                } else if rest.starts_with("don't()") {
+                    enabled = false;
+                } else if let Some(product) = rest.strip_prefix("mul(").and_then(product) {
+                    all += product;
+                    enabled_only += if enabled { product } else { 0 };
+                }
+            }
+            (all, enabled_only)
+        }
+
+        // Pieces of instructions, so that they also get split and glued together
+        fn token((kind, number): (u32, u32)) -> String {
+            match kind {
+                0 => format!("mul({},{})", number % 1000, number / 1000),
+                1 => "mul(".to_string(),
+                2 => number.to_string(),
+                3 => ",".to_string(),
+                4 => ")".to_string(),
+                5 => "do()".to_string(),
+                6 => "don't()".to_string(),
+                _ => "x".to_string(),
+            }
+        }
+
+        property::check(
+            |rng| {
+                rng.vec(0..=30, |rng| {
+                    (rng.range(0..=7) as u32, rng.range(0..=999_999) as u32)
+                })
+            },
+            |tokens| {
+                let input: String = tokens.iter().copied().map(token).collect();
+                let problem = Problem::from_string(&input).unwrap();
+                let (all, enabled_only) = reference(&input);
+
+                property::equal(problem.part_1(), all)?;
+                property::equal(problem.part_2(), Some(enabled_only))
+            },
+        );
+    }
+}
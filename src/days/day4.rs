@@ -0,0 +1,217 @@
+use crate::geometry::{Direction, Vec2};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
+use crate::solution::{ParamKind, Params, Solution};
+
+/// Word searched for in part 1
+const WORD: &str = "XMAS";
+
+pub struct Problem {
+    grid: Grid<char>,
+    word: String,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            grid: parse::grid(string, |char| char)?,
+            word: WORD.to_string(),
+        })
+    }
+
+    fn char_at(&self, x: usize, y: usize) -> Option<&char> {
+        self.grid.get(x, y)
+    }
+
+    fn word_count(&self, word: &str) -> usize {
+        self.grid
+            .iter()
+            // Minimal optimization: only search for chars matching first letter of the word
+            .filter(|(_, &char)| word.starts_with(char))
+            .map(|((x, y), _)| {
+                let start = Vec2::new(x as i32, y as i32);
+
+                Direction::ALL
+                    .iter()
+                    .filter(|direction| {
+                        (0..word.len())
+                            .filter_map(|i| self.grid.at(start + direction.delta() * i as i32))
+                            .collect::<String>()
+                            == word
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 4;
+    const SAMPLE: &'static str = include_str!("../../samples/day4.txt");
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[("word", ParamKind::Text)];
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    /// The word of part 1 can be overridden by the `word` parameter
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let mut problem = Self::from_string(input)?;
+        if let Some(word) = params.text("word") {
+            problem.word = word.to_string();
+        }
+
+        Ok(problem)
+    }
+
+    fn part_1(&self) -> usize {
+        self.word_count(&self.word)
+    }
+
+    /// Lazy implementation for part 2
+    fn part_2(&self) -> Option<usize> {
+        Some(
+            self.grid
+                .find_all(&'A') // only look at tiles containing an A
+                .filter(|(x, y)| *x > 0 && *y > 0) // ignore first row and column (prevents overflow)
+                .filter(|(x, y)| {
+                    let seq = [
+                        self.char_at(x - 1, y - 1),
+                        self.char_at(x + 1, y - 1),
+                        self.char_at(x - 1, y + 1),
+                        self.char_at(x + 1, y + 1),
+                    ]
+                    .iter()
+                    .filter_map(|&char| char) // Remove nones
+                    .collect::<String>();
+
+                    seq == "MMSS" || seq == "SSMM" || seq == "SMSM" || seq == "MSMS"
+                })
+                .count(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(18, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_word_param() {
+        // Searching in all directions finds a word as often as its reverse
+        let params = "word=\"SAMX\"".parse().unwrap();
+        let problem = Problem::parse_with(Problem::SAMPLE, &params).unwrap();
+        assert_eq!(18, problem.part_1());
+
+        let params = "word=\"\"".parse().unwrap();
+        let problem = Problem::parse_with(Problem::SAMPLE, &params).unwrap();
+        assert_eq!(0, problem.part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(9),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+    }
+
+    #[test]
+    fn test_char_at() {
+        let problem = Problem::from_string(
+            "123\n\
+            456\n\
+            789",
+        )
+        .unwrap();
+
+        assert_eq!(Some(&'1'), problem.char_at(0, 0));
+        assert_eq!(Some(&'2'), problem.char_at(1, 0));
+        assert_eq!(Some(&'3'), problem.char_at(2, 0));
+        assert_eq!(Some(&'4'), problem.char_at(0, 1));
+        assert_eq!(Some(&'5'), problem.char_at(1, 1));
+        assert_eq!(Some(&'6'), problem.char_at(2, 1));
+        assert_eq!(Some(&'7'), problem.char_at(0, 2));
+        assert_eq!(Some(&'8'), problem.char_at(1, 2));
+        assert_eq!(Some(&'9'), problem.char_at(2, 2));
+    }
+
+    #[test]
+    fn test_property_reference() {
+        const LETTERS: [char; 5] = ['X', 'M', 'A', 'S', '.'];
+
+        property::check(
+            |rng| {
+                let width = rng.range(1..=6) as u8;
+                let cells = rng.vec(0..=36, |rng| rng.below(LETTERS.len()) as u8);
+                let word = rng.vec(0..=4, |rng| rng.below(4) as u8);
+                (width, cells, word)
+            },
+            |(width, cells, word)| {
+                let letter = |&index: &u8| LETTERS[index as usize % LETTERS.len()];
+                let width = (*width as usize).max(1);
+                let rows: Vec<Vec<char>> = cells
+                    .chunks_exact(width)
+                    .map(|row| row.iter().map(letter).collect())
+                    .collect();
+                if rows.is_empty() {
+                    return Ok(());
+                }
+                let word: String = word.iter().map(letter).collect();
+
+                let input: String = rows
+                    .iter()
+                    .map(|row| row.iter().collect::<String>() + "\n")
+                    .collect();
+                let params = format!("word=\"{}\"", word).parse().unwrap();
+                let problem = Problem::parse_with(&input, &params).unwrap();
+
+                // Reads every row, column and diagonal, then counts the word both ways in them
+                let mut lines: HashMap<(u8, isize), String> = HashMap::new();
+                for (y, row) in rows.iter().enumerate() {
+                    for (x, &char) in row.iter().enumerate() {
+                        let (x, y) = (x as isize, y as isize);
+                        for key in [(0, y), (1, x), (2, x - y), (3, x + y)] {
+                            lines.entry(key).or_default().push(char);
+                        }
+                    }
+                }
+                let reversed: String = word.chars().rev().collect();
+                let occurrences = |line: &String, word: &str| {
+                    (0..line.len())
+                        .filter(|&start| !word.is_empty() && line[start..].starts_with(word))
+                        .count()
+                };
+                let words = lines
+                    .values()
+                    .map(|line| occurrences(line, &word) + occurrences(line, &reversed))
+                    .sum();
+
+                // Checks both diagonals through every A that isn't on the border
+                let crosses = (1..rows.len().saturating_sub(1))
+                    .flat_map(|y| (1..width - 1).map(move |x| (x, y)))
+                    .filter(|&(x, y)| {
+                        let diagonal = |a: char, b: char| {
+                            matches!((a, rows[y][x], b), ('M', 'A', 'S') | ('S', 'A', 'M'))
+                        };
+                        diagonal(rows[y - 1][x - 1], rows[y + 1][x + 1])
+                            && diagonal(rows[y - 1][x + 1], rows[y + 1][x - 1])
+                    })
+                    .count();
+
+                property::equal(problem.part_1(), words)?;
+                property::equal(problem.part_2(), Some(crosses))
+            },
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Problem {
+    rules: HashMap<u32, Vec<u32>>,
+    updates: Vec<Vec<u32>>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let (top, bottom) = parse::two_sections(
+            string,
+            "page ordering rules and updates separated by a blank line",
+        )?;
+
+        Ok(Self {
+            rules: top.iter().try_fold(
+                HashMap::new(),
+                |mut rules: HashMap<u32, Vec<u32>>, line| {
+                    let (page_left, page_right) = line.split_once(line.text, "|")?;
+
+                    rules
+                        .entry(line.parse(page_left, "a page number")?)
+                        .or_default()
+                        .push(line.parse(page_right, "a page number")?);
+
+                    Ok(rules)
+                },
+            )?,
+            updates: bottom
+                .iter()
+                .map(|line| {
+                    line.text
+                        .split(",")
+                        .map(|page| line.parse(page, "a page number"))
+                        .collect()
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn valid_updates(&self) -> Vec<&Vec<u32>> {
+        self.filter_updates(true)
+    }
+
+    fn invalid_updates(&self) -> Vec<&Vec<u32>> {
+        self.filter_updates(false)
+    }
+
+    fn filter_updates(&self, keep_valid: bool) -> Vec<&Vec<u32>> {
+        self.updates
+            .iter()
+            .filter(|update| {
+                let invalid_page = update
+                    .iter()
+                    .enumerate()
+                    // For each page in the update search for incorrect preceding pages
+                    .find(|(update_page_idx, update_page)| {
+                        match self.rules.get(update_page) {
+                            // No ordering rule found, so this page is correct
+                            None => false,
+                            // Ordering rules found for given page
+                            Some(invalid_pages) => invalid_pages
+                                .iter()
+                                .find(|invalid_page| {
+                                    // Check preceding pages for invalid pages
+                                    update[0..*update_page_idx].contains(invalid_page)
+                                })
+                                .is_some(),
+                        }
+                    });
+
+                // None indicates that no invalidly ordered pages were found
+                (invalid_page.is_none() && keep_valid) || (invalid_page.is_some() && !keep_valid)
+            })
+            .collect()
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 5;
+    const SAMPLE: &'static str = include_str!("../../samples/day5.txt");
+
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> u32 {
+        self.valid_updates()
+            .iter()
+            .map(|update| update[(update.len() - 1) / 2]) // Take the middle page
+            .sum()
+    }
+
+    fn part_2(&self) -> Option<u32> {
+        Some(
+            self.invalid_updates()
+                .iter()
+                .map(|&update| {
+                    let mut sorted_update = update.clone();
+
+                    sorted_update.sort_by(|left, right| match self.rules.get(left) {
+                        None => Ordering::Equal,
+                        Some(disallowed_preceding) => match disallowed_preceding.contains(right) {
+                            true => Ordering::Less,
+                            false => Ordering::Equal,
+                        },
+                    });
+
+                    sorted_update
+                })
+                .map(|update| update[(update.len() - 1) / 2]) // Take the middle page
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_sample_part_1() {
+        assert_eq!(143, Problem::from_string(Problem::SAMPLE).unwrap().part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        assert_eq!(
+            Some(123),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_2()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Problem::from_string("47|53\n97-13\n\n75,47").err().unwrap();
+        assert_eq!((2, 6), (error.line, error.column));
+        assert_eq!("expected '|', found end of line", error.message);
+
+        assert!(Problem::from_string("47|53\n97|13").is_err());
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Hidden order of the pages, the rules give it for every pair of pages that get printed
+        let key = |page: u32| page * 37 % 101;
+
+        property::check(
+            |rng| {
+                rng.vec(1..=6, |rng| {
+                    let mut update = rng.vec(1..=7, |rng| rng.range(0..=99) as u32);
+                    if rng.bool() {
+                        update.sort_by_key(|&page| key(page));
+                    }
+                    update
+                })
+            },
+            |updates| {
+                let updates: Vec<Vec<u32>> = updates
+                    .iter()
+                    .map(|update| {
+                        // Pages are printed at most once per update
+                        let mut seen = HashSet::new();
+                        update
+                            .iter()
+                            .copied()
+                            .filter(|&page| seen.insert(page))
+                            .collect()
+                    })
+                    .filter(|update: &Vec<u32>| !update.is_empty())
+                    .collect();
+                let mut pages: Vec<u32> = updates.iter().flatten().copied().collect();
+                pages.sort_by_key(|&page| key(page));
+                pages.dedup();
+                if updates.is_empty() || pages.len() < 2 {
+                    return Ok(());
+                }
+
+                let rules: String = pages
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, before)| pages[i + 1..].iter().map(move |after| (before, after)))
+                    .map(|(before, after)| format!("{}|{}\n", before, after))
+                    .collect();
+                let printed: String = updates
+                    .iter()
+                    .map(|update| {
+                        let pages: Vec<String> = update.iter().map(u32::to_string).collect();
+                        pages.join(",") + "\n"
+                    })
+                    .collect();
+                let problem = Problem::from_string(&format!("{}\n{}", rules, printed)).unwrap();
+
+                // The middle page is the one preceded by half of the other pages once ordered
+                let middle = |update: &Vec<u32>| {
+                    *update
+                        .iter()
+                        .find(|&&page| {
+                            let preceding = update.iter().filter(|&&other| key(other) < key(page));
+                            preceding.count() == (update.len() - 1) / 2
+                        })
+                        .unwrap()
+                };
+                let ordered = |update: &&Vec<u32>| update.windows(2).all(|w| key(w[0]) < key(w[1]));
+
+                property::equal(
+                    problem.part_1(),
+                    updates.iter().filter(ordered).map(middle).sum(),
+                )?;
+                property::equal(
+                    problem.part_2(),
+                    Some(updates.iter().filter(|u| !ordered(u)).map(middle).sum()),
+                )
+            },
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::animate::{Animate, Frame, Frames};
+use crate::geometry::{Direction, Vec2};
+use crate::grid::Grid;
+use crate::metrics;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::{HashMap, HashSet};
+use std::iter;
+
+type Position = Vec2<i32>;
+
+#[derive(Debug)]
+pub struct Problem {
+    bounds: (Position, Position),
+    position: Position,
+    direction: Position,
+    obstructions: HashMap<Position, bool>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let grid = parse::grid(string, |char| char)?;
+        let (x, y) = grid
+            .find(&'^')
+            .ok_or_else(|| ParseError::missing(string, "a starting position '^'"))?;
+
+        Ok(Self {
+            bounds: (
+                Vec2::ZERO,
+                Vec2::new((grid.width() - 1) as i32, (grid.height() - 1) as i32),
+            ),
+            position: Vec2::new(x as i32, y as i32),
+            direction: Direction::North.delta(),
+            obstructions: grid
+                .find_all(&'#')
+                .map(|(x, y)| (Vec2::new(x as i32, y as i32), true))
+                .collect(),
+        })
+    }
+
+    fn in_bounds(&self, position: Position) -> bool {
+        position.x >= self.bounds.0.x
+            && position.x <= self.bounds.1.x
+            && position.y >= self.bounds.0.y
+            && position.y <= self.bounds.1.y
+    }
+
+    fn traverse(
+        &self,
+        obstructions: &HashMap<Position, bool>,
+        mut move_callback: impl FnMut(Position),
+        mut turn_callback: impl FnMut(Position, Position) -> bool,
+    ) -> bool {
+        let mut direction = self.direction;
+        let mut position = self.position;
+
+        loop {
+            // Determine the next position, but do not move there yet
+            let new_position = position + direction;
+
+            // Check whether the new position lies on the map, if not stop
+            if !self.in_bounds(new_position) {
+                // Return true to indicate the traversal stopped naturally (reaching map boundary)
+                return true;
+            }
+
+            // Obstruction at new position, so rotate direction vector 90 degrees clockwise
+            if obstructions.get(&new_position).is_some() {
+                direction = direction.rotate_right(); // (x,y) = (-y, x)
+                trace::event!(Trace, "turned", position = position, direction = direction);
+
+                // Turn callback can also function as circuit breaker
+                if turn_callback(position, direction) {
+                    // Return false to indicate the traversal was stopped prematurely
+                    return false;
+                }
+
+                continue;
+            }
+
+            // Move to the new position
+            position = new_position;
+
+            move_callback(position);
+        }
+    }
+
+    /// Positions visited by the guard, stopping once they walk in a loop if they never leave
+    fn traversal_path(&self) -> Vec<Position> {
+        let mut visited: Vec<Position> = vec![self.position];
+        let mut turns = HashSet::new();
+
+        self.traverse(
+            &self.obstructions,
+            |position| {
+                if !visited.contains(&position) {
+                    visited.push(position);
+                }
+            },
+            |position, direction| !turns.insert((position, direction)),
+        );
+        trace::event!(
+            Debug,
+            "walked",
+            visited = visited.len(),
+            turns = turns.len()
+        );
+
+        visited
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 6;
+    const SAMPLE: &'static str = include_str!("../../samples/day6.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.traversal_path().len()
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        Some(
+            self.traversal_path()
+                .iter()
+                // No obstruction can be placed where the guard stands
+                .filter(|&&position| position != self.position)
+                .filter(move |&&position| {
+                    // Insert an obstruction at each unique position sequentially, then check for loops
+                    let mut new_obstructions = self.obstructions.clone();
+                    new_obstructions.insert(position, true);
+
+                    let mut visited: HashMap<(Position, Position), bool> = HashMap::new();
+                    visited.insert((self.position, self.direction), true);
+
+                    // Only include obstruction variations whose traversal was not completed due to loop detection
+                    let left = metrics::time("day6.loop_checks", || {
+                        self.traverse(
+                            &new_obstructions,
+                            |_| (),
+                            |position, direction| {
+                                // Detect loops by checking whether a position was already visited with the same direction
+                                if visited.contains_key(&(position, direction)) {
+                                    return true;
+                                }
+
+                                visited.insert((position, direction), true);
+
+                                false
+                            },
+                        )
+                    });
+                    trace::event!(
+                        Debug,
+                        "checked obstruction",
+                        position = position,
+                        loops = !left
+                    );
+
+                    !left
+                })
+                .count(),
+        )
+    }
+}
+
+impl Animate for Problem {
+    /// The guard's walk, one frame per step or turn, with the visited positions marked by 'X'
+    fn frames(self) -> Frames {
+        let cell = |position: Position| (position.x as usize, position.y as usize);
+        let (width, height) = cell(self.bounds.1 + Vec2::new(1, 1));
+        let mut grid = Grid::new(width, height, vec!['.'; width * height]);
+        for &obstruction in self.obstructions.keys() {
+            grid[cell(obstruction)] = '#';
+        }
+
+        let mut position = self.position;
+        let mut direction = self.direction;
+        let mut visited = HashSet::from([position]);
+        let mut step = 0;
+        let mut done = false;
+
+        Box::new(iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            grid[cell(position)] = 'X';
+            let mut frame = Frame {
+                grid: grid.clone(),
+                status: format!("Step {}: {} positions visited", step, visited.len()),
+            };
+            frame.grid[cell(position)] = Direction::from_delta(direction)
+                .and_then(Direction::arrow)
+                .unwrap_or('?');
+
+            let new_position = position + direction;
+            if !self.in_bounds(new_position) {
+                done = true;
+            } else if self.obstructions.contains_key(&new_position) {
+                direction = direction.rotate_right();
+            } else {
+                position = new_position;
+                visited.insert(position);
+            }
+            step += 1;
+
+            Some(frame)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(Vec2::new(4, 6), problem.position);
+        assert_eq!(41, problem.part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(Some(6), problem.part_2());
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames: Vec<_> = Problem::from_string(Problem::SAMPLE)
+            .unwrap()
+            .frames()
+            .collect();
+
+        assert_eq!('^', frames[0].grid[(4, 6)]);
+        assert_eq!('>', frames[6].grid[(4, 1)]);
+        assert_eq!('X', frames[6].grid[(4, 2)]);
+        assert_eq!(
+            "Step 54: 41 positions visited",
+            frames.last().unwrap().status
+        );
+    }
+
+    #[test]
+    fn test_guard_in_loop() {
+        // Found by fuzzing, the guard never leaves this map
+        let problem = Problem::from_string(".#..\n...#\n#^..\n..#.").unwrap();
+
+        assert_eq!(4, problem.part_1());
+    }
+
+    #[test]
+    fn test_obstruction_at_start() {
+        // Found by the property test, blocking the start used to trap the guard coming back
+        let problem = Problem::from_string("###\n#.#\n#^#\n#.#").unwrap();
+
+        assert_eq!(Some(1), problem.part_2());
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Walks step by step, giving the visited positions and whether the guard left the map
+        fn walk(blocked: &[Vec<bool>], start: (i32, i32)) -> (HashSet<(i32, i32)>, bool) {
+            let (mut position, mut direction) = (start, (0, -1));
+            let (mut states, mut visited) = (HashSet::new(), HashSet::new());
+
+            while states.insert((position, direction)) {
+                visited.insert(position);
+                let (x, y) = (position.0 + direction.0, position.1 + direction.1);
+                match blocked.get(y as usize).and_then(|row| row.get(x as usize)) {
+                    None => return (visited, true),
+                    Some(true) => direction = (-direction.1, direction.0),
+                    Some(false) => position = (x, y),
+                }
+            }
+            (visited, false)
+        }
+
+        property::check(
+            |rng| {
+                let width = rng.range(1..=7) as u8;
+                let cells = rng.vec(1..=49, |rng| rng.below(5) as u8);
+                (width, cells, rng.below(49) as u8)
+            },
+            |(width, cells, start)| {
+                let width = (*width as usize).max(1);
+                let mut blocked: Vec<Vec<bool>> = cells
+                    .chunks_exact(width)
+                    .map(|row| row.iter().map(|&cell| cell == 0).collect())
+                    .collect();
+                if blocked.is_empty() {
+                    return Ok(());
+                }
+                let start = *start as usize % (width * blocked.len());
+                let start = ((start % width) as i32, (start / width) as i32);
+                blocked[start.1 as usize][start.0 as usize] = false;
+
+                let input: String = blocked
+                    .iter()
+                    .enumerate()
+                    .map(|(y, row)| {
+                        let tile = |(x, &blocked)| match blocked {
+                            true => '#',
+                            false if (x as i32, y as i32) == start => '^',
+                            false => '.',
+                        };
+                        row.iter().enumerate().map(tile).collect::<String>() + "\n"
+                    })
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+
+                let (visited, left) = walk(&blocked, start);
+                property::equal(problem.part_1(), visited.len())?;
+                if !left {
+                    // Part 2 assumes that the guard leaves the map without a new obstruction
+                    return Ok(());
+                }
+
+                // Tries an obstruction on each free tile except the start, counting the loops
+                let mut obstructed = blocked.clone();
+                let mut loops = 0;
+                for y in 0..blocked.len() {
+                    for x in 0..width {
+                        if blocked[y][x] || (x as i32, y as i32) == start {
+                            continue;
+                        }
+                        obstructed[y][x] = true;
+                        loops += !walk(&obstructed, start).1 as usize;
+                        obstructed[y][x] = false;
+                    }
+                }
+
+                property::equal(problem.part_2(), Some(loops))
+            },
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::metrics;
+use crate::parse::{self, Line, ParseError};
+use crate::solution::Solution;
+use crate::trace;
+
+#[derive(Debug)]
+enum Operator {
+    Add,
+    Mul,
+    Concat,
+}
+
+#[derive(Debug)]
+struct Equation {
+    outcome: usize,
+    operands: Vec<usize>,
+}
+
+impl Equation {
+    fn from_line(line: &Line) -> Result<Self, ParseError> {
+        let (left, right) = line.split_once(line.text, ": ")?;
+        let operands: Vec<usize> = right
+            .split_whitespace()
+            .map(|operand| line.parse(operand, "an operand"))
+            .collect::<Result<_, _>>()?;
+
+        // Operators are applied to pairs of operands, so a single operand can't be evaluated
+        if operands.len() < 2 {
+            return Err(line.expected_at_end("at least two operands"));
+        }
+
+        Ok(Self {
+            outcome: line.parse(left, "a test value")?,
+            operands,
+        })
+    }
+
+    fn is_solvable(&self, operators: &[Operator]) -> bool {
+        metrics::count("day7.is_solvable_calls");
+
+        operators
+            .iter()
+            .find(|operator| {
+                // Results too large to represent can't match the outcome
+                let evaluated = match operator {
+                    Operator::Add => self.operands[0].checked_add(self.operands[1]),
+                    Operator::Mul => self.operands[0].checked_mul(self.operands[1]),
+                    Operator::Concat => format!("{}{}", self.operands[0], self.operands[1])
+                        .parse()
+                        .ok(),
+                };
+                let Some(evaluated) = evaluated else {
+                    return false;
+                };
+
+                // If these were the last operands, compare without expected outcome
+                if self.operands.len() == 2 {
+                    return evaluated == self.outcome;
+                }
+
+                // Otherwise replace the two evaluated operands with their result and recurse
+                let new_eqn = Equation {
+                    outcome: self.outcome,
+                    operands: [vec![evaluated], self.operands[2..].to_vec()].concat(),
+                };
+                new_eqn.is_solvable(operators)
+            })
+            .is_some()
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    equations: Vec<Equation>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            equations: parse::lines(string)
+                .map(|line| Equation::from_line(&line))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Sum of the outcomes of the equations that can be solved with the given operators
+    fn calibration(&self, operators: &[Operator]) -> usize {
+        self.equations
+            .iter()
+            .filter(|eqn| {
+                let solvable = eqn.is_solvable(operators);
+                trace::event!(
+                    Debug,
+                    "checked equation",
+                    outcome = eqn.outcome,
+                    operands = ?eqn.operands,
+                    solvable = solvable
+                );
+
+                solvable
+            })
+            .map(|eqn| eqn.outcome)
+            .sum()
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 7;
+    const SAMPLE: &'static str = include_str!("../../samples/day7.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.calibration(&[Operator::Add, Operator::Mul])
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        Some(self.calibration(&[Operator::Add, Operator::Mul, Operator::Concat]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(3749, problem.part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(Some(11387), problem.part_2());
+    }
+
+    #[test]
+    fn test_equation_is_solvable() {
+        let operators = [Operator::Add, Operator::Mul];
+        assert!(Equation::from_line(&Line::new(1, "190: 10 19"))
+            .unwrap()
+            .is_solvable(&operators));
+        assert!(Equation::from_line(&Line::new(1, "3267: 81 40 27"))
+            .unwrap()
+            .is_solvable(&operators));
+        assert!(!Equation::from_line(&Line::new(1, "21037: 9 7 18 13"))
+            .unwrap()
+            .is_solvable(&operators));
+    }
+
+    #[test]
+    fn test_equation_overflow() {
+        // Found by fuzzing, concatenating these operands doesn't fit in a usize
+        let equation =
+            Equation::from_line(&Line::new(1, "7290: 6188888888888190 16 10 0")).unwrap();
+
+        assert!(!equation.is_solvable(&[Operator::Add, Operator::Mul, Operator::Concat]));
+    }
+
+    #[test]
+    fn test_property_reference() {
+        // Evaluates every combination of operators, encoded as the digits of a counter
+        fn solvable(outcome: u64, operands: &[u64], operator_count: u32) -> bool {
+            (0..operator_count.pow(operands.len() as u32 - 1)).any(|mut combination| {
+                let evaluated = operands[1..].iter().fold(operands[0], |acc, &operand| {
+                    let operator = combination % operator_count;
+                    combination /= operator_count;
+                    match operator {
+                        0 => acc + operand,
+                        1 => acc * operand,
+                        _ => format!("{}{}", acc, operand).parse().unwrap(),
+                    }
+                });
+                evaluated == outcome
+            })
+        }
+
+        property::check(
+            |rng| {
+                rng.vec(1..=8, |rng| {
+                    let operands = rng.vec(2..=5, |rng| rng.range(1..=20) as u64);
+                    // Aim for outcomes that are reachable now and then
+                    let outcome = match rng.bool() {
+                        true => operands.iter().sum(),
+                        false => rng.range(1..=5000) as u64,
+                    };
+                    (outcome, operands)
+                })
+            },
+            |equations| {
+                let equations: Vec<&(u64, Vec<u64>)> = equations
+                    .iter()
+                    .filter(|(_, operands)| operands.len() >= 2)
+                    .collect();
+                let input: String = equations
+                    .iter()
+                    .map(|(outcome, operands)| {
+                        let operands: Vec<String> = operands.iter().map(u64::to_string).collect();
+                        format!("{}: {}\n", outcome, operands.join(" "))
+                    })
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+                let total = |operator_count| {
+                    equations
+                        .iter()
+                        .filter(|(outcome, operands)| solvable(*outcome, operands, operator_count))
+                        .map(|(outcome, _)| *outcome as usize)
+                        .sum()
+                };
+
+                property::equal(problem.part_1(), total(2))?;
+                property::equal(problem.part_2(), Some(total(3)))
+            },
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::geometry::Vec2;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+type Position = Vec2<isize>;
+
+#[derive(Debug)]
+pub struct Problem {
+    width: usize,
+    height: usize,
+    antennas: HashMap<char, Vec<Position>>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let grid = parse::grid(string, |char| char)?;
+
+        Ok(Self {
+            width: grid.width(),
+            height: grid.height(),
+            antennas: grid.iter().filter(|(_, &char)| char != '.').fold(
+                HashMap::new(),
+                |mut acc, ((x, y), &char)| {
+                    acc.entry(char)
+                        .or_insert(vec![])
+                        .push(Position::new(x as isize, y as isize));
+                    acc
+                },
+            ),
+        })
+    }
+
+    fn in_bounds(&self, position: Position) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as usize) < self.width
+            && (position.y as usize) < self.height
+    }
+}
+
+/// Greatest common divisor, always positive unless both numbers are zero
+fn gcd(a: isize, b: isize) -> isize {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 8;
+    const SAMPLE: &'static str = include_str!("../../samples/day8.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.antennas
+            .iter()
+            .flat_map(|(&_char, positions)| {
+                positions
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(i, &antenna1)| {
+                        positions[i + 1..].iter().flat_map(move |&antenna2| {
+                            let delta = antenna2 - antenna1;
+                            [antenna1 - delta, antenna2 + delta]
+                                .into_iter()
+                                .filter(|antinode| self.in_bounds(*antinode))
+                        })
+                    })
+            })
+            .collect::<HashSet<Position>>() // Deduplicate antinode positions
+            .len()
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        Some(
+            self.antennas
+                .iter()
+                .flat_map(|(&_char, positions)| {
+                    positions
+                        .iter()
+                        .enumerate()
+                        .flat_map(move |(i, &antenna1)| {
+                            positions[i + 1..].iter().flat_map(move |&antenna2| {
+                                // Smallest step along the line, so that no grid position is skipped
+                                let delta = antenna2 - antenna1;
+                                let delta = delta / gcd(delta.x, delta.y);
+                                let mut antinodes = vec![];
+
+                                // Extrapolate backward
+                                let mut antinode = antenna1;
+                                while self.in_bounds(antinode) {
+                                    antinodes.push(antinode);
+                                    antinode -= delta;
+                                }
+
+                                // Extrapolate forward, through the positions between both antennas
+                                antinode = antenna1 + delta;
+                                while self.in_bounds(antinode) {
+                                    antinodes.push(antinode);
+                                    antinode += delta;
+                                }
+
+                                antinodes
+                            })
+                        })
+                })
+                .collect::<HashSet<Position>>() // Deduplicate antinode positions
+                .len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(14, problem.part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(Some(34), problem.part_2());
+    }
+
+    #[test]
+    fn test_antinodes_between() {
+        // Found by the property test, part 2 used to skip the positions between far apart antennas
+        let problem = Problem::from_string("a....\n.....\n..a..").unwrap();
+
+        assert_eq!(Some(3), problem.part_2());
+    }
+
+    #[test]
+    fn test_property_reference() {
+        const TILES: [char; 4] = ['.', '.', 'a', 'B'];
+
+        property::check(
+            |rng| {
+                let width = rng.range(1..=8) as u8;
+                (
+                    width,
+                    rng.vec(0..=64, |rng| rng.below(TILES.len() + 1) as u8),
+                )
+            },
+            |(width, cells)| {
+                let width = (*width as usize).max(1);
+                let rows: Vec<Vec<char>> = cells
+                    .chunks_exact(width)
+                    .map(|row| row.iter().map(|&cell| TILES[cell as usize % 4]).collect())
+                    .collect();
+                if rows.is_empty() {
+                    return Ok(());
+                }
+                let input: String = rows
+                    .iter()
+                    .map(|row| row.iter().collect::<String>() + "\n")
+                    .collect();
+                let problem = Problem::from_string(&input).unwrap();
+
+                let antennas: Vec<(char, isize, isize)> = (0..rows.len())
+                    .flat_map(|y| (0..width).map(move |x| (x, y)))
+                    .filter(|&(x, y)| rows[y][x] != '.')
+                    .map(|(x, y)| (rows[y][x], x as isize, y as isize))
+                    .collect();
+                let pairs = || {
+                    antennas.iter().enumerate().flat_map(|(i, a)| {
+                        antennas[i + 1..]
+                            .iter()
+                            .filter(move |b| a.0 == b.0)
+                            .map(move |b| (a, b))
+                    })
+                };
+
+                // Checks every position against every pair of antennas of the same frequency
+                let (mut twice_as_far, mut in_line) = (0, 0);
+                for (x, y) in
+                    (0..rows.len() as isize).flat_map(|y| (0..width as isize).map(move |x| (x, y)))
+                {
+                    twice_as_far += pairs().any(|(&(_, ax, ay), &(_, bx, by))| {
+                        (x, y) == (2 * ax - bx, 2 * ay - by) || (x, y) == (2 * bx - ax, 2 * by - ay)
+                    }) as usize;
+                    in_line += pairs().any(|(&(_, ax, ay), &(_, bx, by))| {
+                        (bx - ax) * (y - ay) == (by - ay) * (x - ax)
+                    }) as usize;
+                }
+
+                property::equal(problem.part_1(), twice_as_far)?;
+                property::equal(problem.part_2(), Some(in_line))
+            },
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+struct Filesystem {
+    sectors: Vec<Option<u32>>,
+}
+
+impl Filesystem {
+    fn new() -> Self {
+        Self { sectors: vec![] }
+    }
+
+    fn compact_sectors(&self) -> Self {
+        let files = self.sectors.iter().filter(|sector| sector.is_some());
+        let file_count = files.clone().count();
+
+        let mut right = files.rev();
+
+        Self {
+            sectors: [
+                self.sectors[0..file_count]
+                    .iter()
+                    .map(|&sector| {
+                        match sector {
+                            None => *right.next().unwrap(), // Free space -> fill from right
+                            Some(_) => sector,
+                        }
+                    })
+                    .collect(),
+                vec![None; self.sectors.len() - file_count], // Pad with empty space
+            ]
+            .concat(),
+        }
+    }
+
+    fn compact_blocks(&self) -> Self {
+        let mut compacted = self.sectors.clone();
+
+        self.sectors
+            .iter()
+            .cloned()
+            .enumerate()
+            .collect::<Vec<(usize, Option<u32>)>>()
+            .chunk_by(|a, b| a.1 == b.1)
+            .filter(|chunk| chunk.iter().all(|(_, sector)| sector.is_some())) // Only files
+            .rev() // Start from the end
+            .for_each(|file_block| {
+                // Search for an empty block fo fit this file in (search space: begin to file start)
+                if let Some(empty_block) = compacted[0..file_block[0].0]
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .collect::<Vec<(usize, Option<u32>)>>()
+                    .chunk_by(|a, b| a.1 == b.1)
+                    // Only empty blocks
+                    .filter(|chunk| chunk.iter().all(|(_, sector)| sector.is_none()))
+                    // Find empty block to fit file in
+                    .find(|empty_block| empty_block.len() >= file_block.len())
+                {
+                    // Zip empty and file blocks and switch their values
+                    empty_block.iter().zip(file_block).for_each(
+                        |((index_empty, _), (index_file, file_sector))| {
+                            compacted[*index_empty] = *file_sector;
+                            compacted[*index_file] = None;
+                        },
+                    );
+                }
+            });
+
+        Self { sectors: compacted }
+    }
+
+    fn checksum(&self) -> usize {
+        self.sectors
+            .iter()
+            .enumerate()
+            .map(|(i, sector)| i * sector.unwrap_or(0) as usize)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    filesystem: Filesystem,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        let line = parse::lines(string)
+            .next()
+            .ok_or_else(|| ParseError::missing(string, "a disk map"))?;
+
+        Ok(Self {
+            filesystem: line.text.char_indices().enumerate().try_fold(
+                Filesystem::new(),
+                |mut acc, (i, (offset, char))| {
+                    let size = char.to_digit(10).ok_or_else(|| {
+                        line.expected(&line.text[offset..offset + char.len_utf8()], "a digit")
+                    })? as usize;
+
+                    if i % 2 == 0 {
+                        acc.sectors.append(&mut vec![Some(i as u32 / 2); size])
+                    } else {
+                        acc.sectors.append(&mut vec![None; size]);
+                    }
+
+                    Ok(acc)
+                },
+            )?,
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = 9;
+    const SAMPLE: &'static str = include_str!("../../samples/day9.txt");
+
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.filesystem.compact_sectors().checksum()
+    }
+
+    fn part_2(&self) -> Option<usize> {
+        Some(self.filesystem.compact_blocks().checksum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_sample_part_1() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(1928, problem.part_1());
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        let problem = Problem::from_string(Problem::SAMPLE).unwrap();
+
+        assert_eq!(Some(2858), problem.part_2());
+    }
+
+    /// Disk map as a list of sizes, alternating between files and free space
+    fn disk_map(sizes: &[u32]) -> String {
+        sizes.iter().map(|size| size.to_string()).collect()
+    }
+
+    /// Expands the disk map into blocks holding a file ID or nothing
+    fn blocks(sizes: &[u32]) -> Vec<Option<usize>> {
+        sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &size)| vec![(i % 2 == 0).then_some(i / 2); size as usize])
+            .collect()
+    }
+
+    fn checksum(blocks: &[Option<usize>]) -> usize {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| i * block.unwrap_or(0))
+            .sum()
+    }
+
+    #[test]
+    fn test_property_reference() {
+        property::check(
+            |rng| rng.vec(1..=15, |rng| rng.range(0..=9) as u32),
+            |sizes| {
+                let problem = Problem::from_string(&disk_map(sizes)).unwrap();
+
+                // Move the last file block into the first free block until there are no gaps
+                let mut moved = blocks(sizes);
+                while let (Some(free), Some(last)) = (
+                    moved.iter().position(Option::is_none),
+                    moved.iter().rposition(Option::is_some),
+                ) {
+                    if free > last {
+                        break;
+                    }
+                    moved.swap(free, last);
+                }
+
+                // Move whole files, highest ID first, into the leftmost gap that fits them
+                let mut compacted = blocks(sizes);
+                for id in (0..sizes.len().div_ceil(2)).rev() {
+                    let Some(start) = compacted.iter().position(|&b| b == Some(id)) else {
+                        continue;
+                    };
+                    let size = sizes[id * 2] as usize;
+                    if let Some(gap) = (0..start)
+                        .find(|&gap| compacted[gap..gap + size].iter().all(Option::is_none))
+                    {
+                        for offset in 0..size {
+                            compacted.swap(gap + offset, start + offset);
+                        }
+                    }
+                }
+
+                property::equal(problem.part_1(), checksum(&moved))?;
+                property::equal(problem.part_2(), Some(checksum(&compacted)))
+            },
+        );
+    }
+}
@@ -0,0 +1,46 @@
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::solution::Day;
+
+/// All days that have been solved so far, in order
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::of::<day1::Problem>(),
+        Day::of::<day2::Problem>(),
+        Day::of::<day3::Problem>(),
+        Day::of::<day4::Problem>(),
+        Day::of::<day5::Problem>(),
+        Day::of::<day6::Problem>().animated::<day6::Problem>(),
+        Day::of::<day7::Problem>(),
+        Day::of::<day8::Problem>(),
+        Day::of::<day9::Problem>(),
+        Day::of::<day10::Problem>().rendered::<day10::Problem>(),
+        Day::of::<day11::Problem>(),
+        Day::of::<day12::Problem>().rendered::<day12::Problem>(),
+        Day::of::<day13::Problem>(),
+        Day::of::<day14::Problem>()
+            .animated::<day14::Problem>()
+            .rendered::<day14::Problem>(),
+        Day::of::<day15::Problem>().animated::<day15::Problem>(),
+        Day::of::<day16::Problem>().rendered::<day16::Problem>(),
+    ]
+}
+
+pub fn get(number: u8) -> Option<Day> {
+    registry().into_iter().find(|day| day.number == number)
+}
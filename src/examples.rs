@@ -0,0 +1,255 @@
+//! Example inputs with their expected answers, checked by the tests without any code per example.
+//!
+//! Every example is a pair of files in `samples/day<N>/`: the input `<name>.txt` and the sidecar
+//! `<name>.expected`, which declares the answers along with any parameters the day needs. They
+//! live next to the days' puzzle samples rather than in `examples/`, which Cargo reserves for
+//! example programs:
+//!
+//! ```text
+//! part1 12
+//! part2 81
+//! params width=11,height=7
+//! ```
+//!
+//! Parts without a line are not checked. Blank lines and lines starting with `#` are ignored.
+//!
+//! The `sample` example only has a sidecar, as its input is the day's [`Solution::SAMPLE`], which
+//! is solved with the day's sample parameters. Day tests that check more than the answers load
+//! the other inputs with [`input`] rather than repeating them.
+//!
+//! [`Solution::SAMPLE`]: crate::solution::Solution::SAMPLE
+
+use crate::days;
+use crate::parse::{self, ParseError};
+use crate::solution::Params;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DIRECTORY: &str = "samples";
+
+/// Contents of a sidecar file
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Expected {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub params: Params,
+}
+
+impl Expected {
+    pub fn from_string(string: &str) -> Result<Self, ParseError> {
+        let mut expected = Self::default();
+
+        for line in parse::lines(string)
+            .filter(|line| !line.text.trim().is_empty() && !line.text.starts_with('#'))
+        {
+            let (key, value) = line.split_once(line.text.trim(), " ")?;
+            let value = value.trim();
+
+            match key {
+                "part1" => expected.part_1 = Some(value.to_string()),
+                "part2" => expected.part_2 = Some(value.to_string()),
+                "params" => expected.params = line.parse(value, "parameters like width=11")?,
+                _ => return Err(line.expected(key, "'part1', 'part2' or 'params'")),
+            }
+        }
+
+        Ok(expected)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub expected: Expected,
+}
+
+impl Example {
+    /// Solves the example, describing the first answer that differs from the expected one
+    pub fn check(&self) -> Result<(), String> {
+        let day = days::get(self.day).ok_or_else(|| format!("Day {} is not solved", self.day))?;
+        let solver =
+            (day.parse)(&self.input, &self.expected.params).map_err(|error| error.to_string())?;
+
+        let answers = [
+            (1, &self.expected.part_1, Some(solver.part_1())),
+            (2, &self.expected.part_2, solver.part_2()),
+        ];
+        for (part, expected, answer) in answers {
+            match (expected, answer) {
+                (Some(expected), Some(answer)) if answer != expected.as_str() => {
+                    return Err(format!(
+                        "part {}: expected {}, got {}",
+                        part, expected, answer
+                    ))
+                }
+                (Some(expected), None) => {
+                    return Err(format!(
+                        "part {}: expected {}, got no answer",
+                        part, expected
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Example {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{}/{}", self.day, self.name)
+    }
+}
+
+/// Finds all examples in the `day<N>` directories of the given directory, in order of day and name
+pub fn discover(directory: &Path) -> Result<Vec<Example>, String> {
+    let mut examples = vec![];
+
+    for entry in read_dir(directory)? {
+        let Some(day) = entry
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse().ok())
+            .filter(|_| entry.is_dir())
+        else {
+            continue;
+        };
+
+        for path in read_dir(&entry)? {
+            let name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let sample = name == "sample";
+            let input = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("txt") if sample => {
+                    return Err(format!(
+                        "{} copies the sample, which is taken from the day's solution instead",
+                        path.display()
+                    ))
+                }
+                Some("txt") => read(&path)?,
+                Some("expected") if sample => days::get(day)
+                    .ok_or_else(|| format!("Day {} is not solved", day))?
+                    .sample
+                    .to_string(),
+                _ => continue,
+            };
+            let sidecar = path.with_extension("expected");
+            let mut expected = Expected::from_string(&read(&sidecar)?)
+                .map_err(|error| format!("Failed to parse {}: {}", sidecar.display(), error))?;
+            if sample {
+                let day = days::get(day).ok_or_else(|| format!("Day {} is not solved", day))?;
+                let mut params = Params::from(day.sample_params);
+                params.extend(&expected.params);
+                expected.params = params;
+            }
+
+            examples.push(Example {
+                day,
+                name,
+                input,
+                expected,
+            });
+        }
+    }
+
+    examples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+
+    Ok(examples)
+}
+
+/// Input of the named example of the day, for tests that check more than its answers
+pub fn input(day: u8, name: &str) -> Result<String, String> {
+    read(
+        &Path::new(DIRECTORY)
+            .join(format!("day{}", day))
+            .join(format!("{}.txt", name)),
+    )
+}
+
+fn read_dir(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|error| format!("Failed to read {}: {}", directory.display(), error))?;
+
+    Ok(entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let expected =
+            Expected::from_string("# comment\npart1 12\n\nparams width=11,height=7\n").unwrap();
+
+        assert_eq!(Some("12".to_string()), expected.part_1);
+        assert_eq!(None, expected.part_2);
+        assert_eq!(Some(7), expected.params.get("height"));
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                1,
+                "expected 'part1', 'part2' or 'params', found 'part3'"
+            )),
+            Expected::from_string("part3 12")
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let example = |part_2: &str| Example {
+            day: 1,
+            name: "sample".to_string(),
+            input: days::get(1).unwrap().sample.to_string(),
+            expected: Expected {
+                part_1: Some("11".to_string()),
+                part_2: Some(part_2.to_string()),
+                params: Params::default(),
+            },
+        };
+
+        assert_eq!(Ok(()), example("31").check());
+        assert_eq!(
+            Err("part 2: expected 32, got 31".to_string()),
+            example("32").check()
+        );
+    }
+
+    /// Checks every example in the examples directory, reporting the outcome of each
+    #[test]
+    fn test_examples() {
+        let examples = discover(Path::new(DIRECTORY)).unwrap();
+        let mut failed = vec![];
+
+        for example in &examples {
+            match example.check() {
+                Ok(()) => println!("ok      {}", example),
+                Err(message) => {
+                    println!("FAILED  {}: {}", example, message);
+                    failed.push(format!("{}: {}", example, message));
+                }
+            }
+        }
+
+        assert!(!examples.is_empty(), "no examples in {}", DIRECTORY);
+        assert!(
+            failed.is_empty(),
+            "{} of {} examples failed:\n{}",
+            failed.len(),
+            examples.len(),
+            failed.join("\n")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Entry point of the fuzz targets in `fuzz/`, one per day, which feed arbitrary bytes to the
+//! day's parser and then solve both parts of whatever it accepts.
+//!
+//! Run a target with `cargo +nightly fuzz run day3`, which starts from the seed corpus in
+//! `fuzz/corpus/day3`. A crashing input is saved in `fuzz/artifacts/day3`, and can be replayed in a
+//! regular unit test by passing it to [`run`], or by solving it like the day's tests that mention
+//! being found by fuzzing.
+
+use crate::days;
+use crate::solution::Params;
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Longer inputs are skipped, which keeps the time spent per input bounded
+pub const MAX_INPUT: usize = 1024;
+/// Solving a single input taking longer than this counts as a hang
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Parses the bytes as the given day's input and solves both parts if they are accepted. Panics
+/// or aborts when the day doesn't handle the input gracefully.
+pub fn run(day: u8, data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if input.len() > MAX_INPUT {
+        return;
+    }
+    let day = days::get(day).expect("Unknown day");
+
+    // Solve with the sample's parameters, which keep day 14's area small, as its part 2
+    // simulates every second until the robots repeat
+    let params = Params::from(day.sample_params);
+
+    let (done, watchdog) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if watchdog.recv_timeout(TIMEOUT) == Err(mpsc::RecvTimeoutError::Timeout) {
+            eprintln!("Day {} timed out after {:?}", day.number, TIMEOUT);
+            process::abort();
+        }
+    });
+
+    if let Ok(solver) = (day.parse)(input, &params) {
+        solver.part_1();
+        solver.part_2();
+    }
+
+    let _ = done.send(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    /// Replays the checked-in corpus, which contains at least the sample of every day
+    #[test]
+    fn test_corpus() {
+        for day in days::registry() {
+            let directory = Path::new("fuzz/corpus").join(format!("day{}", day.number));
+            let sample = fs::read_to_string(directory.join("sample")).unwrap();
+            assert_eq!(day.sample, sample, "seed of day {} is outdated", day.number);
+
+            for entry in fs::read_dir(&directory).unwrap() {
+                run(day.number, &fs::read(entry.unwrap().path()).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+/// Primitive integer types that can be used as vector components
+pub trait Scalar:
+    Copy
+    + Debug
+    + Display
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Absolute difference between two values, which does not overflow for unsigned types
+    fn distance(self, other: Self) -> Self;
+
+    /// Remainder that is never negative, see `i32::rem_euclid`
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    fn to_usize(self) -> Option<usize>;
+
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn distance(self, other: Self) -> Self {
+                    if self > other { self - other } else { other - self }
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Two-dimensional vector in screen coordinates, so y increases downwards
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// Whether both vectors are horizontally or vertically next to each other
+    pub fn is_adjacent(self, other: Self) -> bool {
+        self.manhattan(other) == T::ONE
+    }
+
+    /// Component-wise euclidean remainder, used to wrap positions around the edges of an area
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        Self::new(self.x.rem_euclid(rhs.x), self.y.rem_euclid(rhs.y))
+    }
+
+    pub fn to_usize(self) -> Option<Vec2<usize>> {
+        Some(Vec2::new(self.x.to_usize()?, self.y.to_usize()?))
+    }
+
+    pub fn from_usize(vec: Vec2<usize>) -> Option<Self> {
+        Some(Self::new(T::from_usize(vec.x)?, T::from_usize(vec.y)?))
+    }
+
+    /// Converts the position to a row-major index, if it lies within an area of the given size
+    pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+        let Vec2 { x, y } = self.to_usize()?;
+
+        (x < width && y < height).then_some(x + y * width)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Vec2<T> {
+    /// Rotates 90 degrees clockwise (as displayed on screen): (x, y) -> (-y, x)
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates 90 degrees counterclockwise (as displayed on screen): (x, y) -> (y, -x)
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(vec: Vec2<T>) -> Self {
+        (vec.x, vec.y)
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Implements a component-wise operator for both vector and scalar right hand sides
+macro_rules! impl_operator {
+    ($trait:ident, $method:ident) => {
+        impl<T: Scalar> $trait<Vec2<T>> for Vec2<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: Vec2<T>) -> Self {
+                Self::new(self.x.$method(rhs.x), self.y.$method(rhs.y))
+            }
+        }
+
+        impl<T: Scalar> $trait<T> for Vec2<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: T) -> Self {
+                Self::new(self.x.$method(rhs), self.y.$method(rhs))
+            }
+        }
+    };
+}
+
+impl_operator!(Add, add);
+impl_operator!(Sub, sub);
+impl_operator!(Mul, mul);
+impl_operator!(Div, div);
+impl_operator!(Rem, rem);
+
+impl<T: Scalar> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Compass directions on a grid, where north points up
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Horizontal and vertical directions in clockwise order
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions in clockwise order
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Parses the arrow characters `^`, `>`, `v` and `<` as used by the puzzles
+    pub fn from_arrow(char: char) -> Option<Self> {
+        match char {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Arrow character of a cardinal direction, the inverse of [`Direction::from_arrow`]
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Direction::North => Some('^'),
+            Direction::East => Some('>'),
+            Direction::South => Some('v'),
+            Direction::West => Some('<'),
+            _ => None,
+        }
+    }
+
+    /// Direction of a unit step, the inverse of [`Direction::delta`]
+    pub fn from_delta<T: Scalar + Neg<Output = T>>(delta: Vec2<T>) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.delta() == delta)
+    }
+
+    /// Unit step in this direction, with diagonals moving along both axes
+    pub fn delta<T: Scalar + Neg<Output = T>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+
+        let component = |value: i8| match value {
+            -1 => -T::ONE,
+            1 => T::ONE,
+            _ => T::ZERO,
+        };
+
+        Vec2::new(component(x), component(y))
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+
+    /// Rotates clockwise by the given number of 45 degree steps
+    fn rotate(self, steps: usize) -> Self {
+        let index = Self::ALL.iter().position(|&dir| dir == self).unwrap();
+
+        Self::ALL[(index + steps) % Self::ALL.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(1, 4);
+
+        assert_eq!(Vec2::new(4, 2), a + b);
+        assert_eq!(Vec2::new(2, -6), a - b);
+        assert_eq!(Vec2::new(6, -4), a * 2);
+        assert_eq!(Vec2::new(3, -8), a * Vec2::new(1, 4));
+        assert_eq!(Vec2::new(1, -1), a / 2);
+        assert_eq!(Vec2::new(1, 0), a % 2);
+        assert_eq!(Vec2::new(13, 8), a + 10);
+        assert_eq!(Vec2::new(-3, 2), -a);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Vec2::new(1u32, 5);
+        let b = Vec2::new(4u32, 3);
+
+        assert_eq!(5, a.manhattan(b));
+        assert_eq!(3, a.chebyshev(b));
+        assert!(Vec2::new(2, 3).is_adjacent(Vec2::new(2, 4)));
+        assert!(!Vec2::new(2, 3).is_adjacent(Vec2::new(3, 4)));
+    }
+
+    #[test]
+    fn test_rem_euclid() {
+        let dims = Vec2::new(11, 7);
+
+        assert_eq!(Vec2::new(10, 6), Vec2::new(-1, -8).rem_euclid(dims));
+        assert_eq!(Vec2::new(1, 0), Vec2::new(12, 7).rem_euclid(dims));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let north = Vec2::new(0, -1);
+
+        assert_eq!(Vec2::new(1, 0), north.rotate_right());
+        assert_eq!(Vec2::new(-1, 0), north.rotate_left());
+        assert_eq!(north, north.rotate_right().rotate_left());
+    }
+
+    #[test]
+    fn test_to_index() {
+        assert_eq!(Some(7), Vec2::new(1, 2).to_index(3, 3));
+        assert_eq!(None, Vec2::new(-1, 2).to_index(3, 3));
+        assert_eq!(None, Vec2::new(3, 0).to_index(3, 3));
+        assert_eq!(None, Vec2::new(0, 3).to_index(3, 3));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Vec2::new(0, -1), Direction::North.delta::<i32>());
+        assert_eq!(Vec2::new(-1, 1), Direction::SouthWest.delta::<i64>());
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::NorthWest, Direction::SouthEast.opposite());
+        assert_eq!(Direction::NorthEast, Direction::NorthWest.turn_right());
+        assert_eq!(Some('v'), Direction::South.arrow());
+        assert_eq!(None, Direction::SouthWest.arrow());
+        assert_eq!(
+            Some(Direction::SouthWest),
+            Direction::from_delta(Vec2::new(-1, 1))
+        );
+        assert_eq!(None, Direction::from_delta(Vec2::new(2, 0)));
+
+        // Turning a direction matches rotating its delta vector
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.delta::<i32>().rotate_right(),
+                direction.turn_right().delta()
+            );
+        }
+    }
+}
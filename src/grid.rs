@@ -0,0 +1,256 @@
+use crate::geometry::{Scalar, Vec2};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell within a grid as (x, y), with the origin in the top left corner
+pub type Cell = (usize, usize);
+
+/// Rectangular grid of cells stored in row-major order
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "Grid cell count mismatch");
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns the row-major index of the given position, if it lies within the grid
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        self.in_bounds(x, y).then_some(x + y * self.width)
+    }
+
+    /// Converts a row-major index to its (x, y) position
+    pub fn position_of(&self, index: usize) -> Cell {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.cells.get(self.index_of(x, y)?)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let index = self.index_of(x, y)?;
+        self.cells.get_mut(index)
+    }
+
+    /// Returns the cell at the given vector position, which may lie outside of the grid
+    pub fn at<S: Scalar>(&self, position: Vec2<S>) -> Option<&T> {
+        self.cells.get(position.to_index(self.width, self.height)?)
+    }
+
+    /// Iterates over all cells in row-major order along with their positions
+    pub fn iter(&self) -> impl Iterator<Item = (Cell, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.position_of(index), cell))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Guard against zero width since chunks() does not accept a chunk size of zero
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(if x < self.width { self.height } else { 0 })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Positions of the horizontally and vertically adjacent cells within the grid, in the order
+    /// left, right, up, down
+    pub fn neighbours_4(&self, x: usize, y: usize) -> impl Iterator<Item = Cell> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    /// Positions of all adjacent cells within the grid, including diagonals, row by row
+    pub fn neighbours_8(&self, x: usize, y: usize) -> impl Iterator<Item = Cell> + '_ {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&delta| delta != (0, 0))
+            .filter_map(move |(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<Cell> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+
+        self.in_bounds(x, y).then_some((x, y))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns the position of the first cell (in row-major order) holding the given value
+    pub fn find(&self, value: &T) -> Option<Cell> {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|index| self.position_of(index))
+    }
+
+    /// Returns the positions of all cells holding the given value
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Cell> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+}
+
+impl<T> Index<Cell> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Cell) -> &T {
+        self.get(x, y).expect("Position out of grid bounds")
+    }
+}
+
+impl<T> IndexMut<Cell> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Cell) -> &mut T {
+        self.get_mut(x, y).expect("Position out of grid bounds")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SAMPLE: &str = "123\n\
+                          456\n\
+                          789";
+
+    #[test]
+    fn test_parse() {
+        let grid = parse::grid(SAMPLE, |char| char.to_digit(10).unwrap()).unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(Some(&1), grid.get(0, 0));
+        assert_eq!(Some(&6), grid.get(2, 1));
+        assert_eq!(Some(&8), grid.get(1, 2));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(0, 3));
+        assert_eq!(9, grid[(2, 2)]);
+        assert_eq!(Some(&8), grid.at(Vec2::new(1, 2)));
+        assert_eq!(None, grid.at(Vec2::new(-1, 2)));
+    }
+
+    fn chars(string: &str) -> Grid<char> {
+        parse::grid(string, |char| char).unwrap()
+    }
+
+    #[test]
+    fn test_parse_trailing_newline() {
+        assert_eq!(chars("ab\ncd\n"), chars("ab\ncd"));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = chars(SAMPLE);
+
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours_4(0, 0).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (2, 1), (1, 0), (1, 2)],
+            grid.neighbours_4(1, 1).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 1), (2, 1), (1, 2)],
+            grid.neighbours_8(2, 2).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbours_8(1, 1).count());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = chars(SAMPLE);
+
+        assert_eq!(Some(&['4', '5', '6'][..]), grid.row(1));
+        assert_eq!(None, grid.row(3));
+        assert_eq!(
+            vec!['2', '5', '8'],
+            grid.column(1).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.column(3).count());
+        assert_eq!(3, grid.rows().count());
+        assert_eq!(3, grid.columns().count());
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = chars("#.#\n.#.");
+
+        assert_eq!(Some((1, 0)), grid.find(&'.'));
+        assert_eq!(None, grid.find(&'@'));
+        assert_eq!(
+            vec![(0, 0), (2, 0), (1, 1)],
+            grid.find_all(&'#').collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("123\n456\n789\n", chars(SAMPLE).to_string());
+    }
+}
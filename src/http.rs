@@ -0,0 +1,328 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: "GET",
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    /// Form encoded POST request
+    pub fn post(url: &str, body: &str) -> Self {
+        Self {
+            method: "POST",
+            url: url.to_string(),
+            headers: vec![(
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            )],
+            body: Some(body.to_string()),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Url(String),
+    /// A header whose name or value contains control characters
+    Header(String),
+    Io(io::Error),
+    Response(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Url(url) => write!(f, "Unsupported URL '{}'", url),
+            Error::Header(name) => write!(
+                f,
+                "Invalid header '{}', which can't contain line breaks or other control characters",
+                name.escape_debug()
+            ),
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Response(message) => write!(f, "Invalid response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Formats a header with its value trimmed. Control characters are rejected, as a line break
+/// would end the header early and pass whatever follows as another header.
+fn header_line(name: &str, value: &str) -> Result<String, Error> {
+    let value = value.trim();
+    if name.is_empty()
+        || name.contains(|char: char| char.is_control() || char == ':')
+        || value.contains(char::is_control)
+    {
+        return Err(Error::Header(name.to_string()));
+    }
+
+    Ok(format!("{}: {}", name, value))
+}
+
+/// Sends HTTP requests. Abstracted so that tests can talk to a local server instead of the
+/// puzzle website.
+pub trait Client {
+    fn send(&self, request: &Request) -> Result<Response, Error>;
+}
+
+impl<C: Client + ?Sized> Client for Box<C> {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        (**self).send(request)
+    }
+}
+
+/// Plain HTTP/1.1 over a TCP connection. Doesn't support TLS, so only usable for `http://` URLs.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TcpClient;
+
+impl Client for TcpClient {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        let (host, path) = request
+            .url
+            .strip_prefix("http://")
+            .map(|rest| rest.split_at(rest.find('/').unwrap_or(rest.len())))
+            .ok_or_else(|| Error::Url(request.url.clone()))?;
+        let body = request.body.as_deref().unwrap_or("");
+
+        let mut message = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n",
+            request.method,
+            if path.is_empty() { "/" } else { path },
+            host,
+            body.len()
+        );
+        for (name, value) in &request.headers {
+            message.push_str(&header_line(name, value)?);
+            message.push_str("\r\n");
+        }
+        message.push_str("\r\n");
+        message.push_str(body);
+
+        let mut stream = TcpStream::connect(host)?;
+        stream.write_all(message.as_bytes())?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        parse_response(&response)
+    }
+}
+
+fn parse_response(response: &str) -> Result<Response, Error> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| Error::Response("missing end of headers".to_string()))?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| Error::Response(format!("invalid status line '{}'", head)))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Delegates to the `curl` executable, which takes care of TLS. The request is passed through
+/// stdin so that the session cookie doesn't show up in the process list.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CurlClient;
+
+impl Client for CurlClient {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        let quote =
+            |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+
+        let mut config = format!(
+            "silent\nshow-error\nrequest = {}\nurl = {}\nwrite-out = \"\\n%{{http_code}}\"\n",
+            request.method,
+            quote(&request.url)
+        );
+        for (name, value) in &request.headers {
+            config.push_str(&format!("header = {}\n", quote(&header_line(name, value)?)));
+        }
+        if let Some(body) = &request.body {
+            config.push_str(&format!("data-raw = {}\n", quote(body)));
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())?;
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(Error::Response(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| Error::Response("missing status code".to_string()))?;
+
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| Error::Response(format!("invalid status code '{}'", status)))?,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Picks the client that supports the scheme of the given URL
+pub fn client_for(url: &str) -> Box<dyn Client> {
+    match url.starts_with("http://") {
+        true => Box::new(TcpClient),
+        false => Box::new(CurlClient),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// Serves the given responses on localhost, one per connection, and passes the received
+    /// requests back. Returns the base URL of the server.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length: ") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                sender.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn test_tcp_client() {
+        let (url, requests) = serve(vec![(200, "hello"), (404, "")]);
+
+        let response = TcpClient
+            .send(&Request::post(&format!("{}/submit", url), "a=1").header("Cookie", "x=y"))
+            .unwrap();
+        assert_eq!(
+            Response {
+                status: 200,
+                body: "hello".to_string()
+            },
+            response
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: x=y\r\n"));
+        assert!(request.ends_with("\r\n\r\na=1"));
+
+        assert_eq!(404, TcpClient.send(&Request::get(&url)).unwrap().status);
+        assert!(requests.recv().unwrap().starts_with("GET / HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_header_line() {
+        assert_eq!(
+            "Cookie: session=abc",
+            header_line("Cookie", " session=abc\n").unwrap()
+        );
+        for (name, value) in [
+            ("Cookie", "session=abc\r\nX-Injected: 1"),
+            ("Cookie", "session=a\0bc"),
+            ("Cook\nie", "session=abc"),
+            ("", "session=abc"),
+        ] {
+            assert!(matches!(header_line(name, value), Err(Error::Header(_))));
+        }
+
+        // Checked before connecting, so nothing is sent
+        let request = Request::get("http://127.0.0.1:1/").header("Cookie", "a\r\nb: c");
+        assert!(matches!(TcpClient.send(&request), Err(Error::Header(_))));
+    }
+
+    #[test]
+    fn test_unsupported_url() {
+        assert!(matches!(
+            TcpClient.send(&Request::get("https://example.com")),
+            Err(Error::Url(_))
+        ));
+    }
+}
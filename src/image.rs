@@ -0,0 +1,300 @@
+//! Dependency free image export of grid states, to binary PPM/PGM or SVG. The output only depends
+//! on the grid, so that it can be compared byte for byte in tests.
+
+use crate::days;
+use crate::grid::Grid;
+use crate::runner::{self, Input};
+use crate::solution::Solution;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    /// Distinct colours to tell categories apart, like the plants of day 12
+    pub const PALETTE: [Rgb; 12] = [
+        Rgb::new(230, 25, 75),
+        Rgb::new(60, 180, 75),
+        Rgb::new(255, 225, 25),
+        Rgb::new(0, 130, 200),
+        Rgb::new(245, 130, 48),
+        Rgb::new(145, 30, 180),
+        Rgb::new(70, 240, 240),
+        Rgb::new(240, 50, 230),
+        Rgb::new(210, 245, 60),
+        Rgb::new(250, 190, 212),
+        Rgb::new(0, 128, 128),
+        Rgb::new(170, 110, 40),
+    ];
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Colour from the palette for the given category, repeating once the palette runs out
+    pub fn category(index: usize) -> Self {
+        Self::PALETTE[index % Self::PALETTE.len()]
+    }
+
+    /// Perceived brightness, using the integer weights of ITU-R BT.601
+    pub fn luma(self) -> u8 {
+        ((299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32) / 1000) as u8
+    }
+
+    pub fn gray(level: u8) -> Self {
+        Self::new(level, level, level)
+    }
+}
+
+/// Image with one pixel per grid cell
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Picture {
+    Color(Grid<Rgb>),
+    Gray(Grid<u8>),
+}
+
+impl Picture {
+    fn width(&self) -> usize {
+        match self {
+            Picture::Color(grid) => grid.width(),
+            Picture::Gray(grid) => grid.width(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Picture::Color(grid) => grid.height(),
+            Picture::Gray(grid) => grid.height(),
+        }
+    }
+
+    fn colors(&self) -> Grid<Rgb> {
+        match self {
+            Picture::Color(grid) => grid.clone(),
+            Picture::Gray(grid) => grid.map(|&level| Rgb::gray(level)),
+        }
+    }
+
+    fn levels(&self) -> Grid<u8> {
+        match self {
+            Picture::Color(grid) => grid.map(|color| color.luma()),
+            Picture::Gray(grid) => grid.clone(),
+        }
+    }
+}
+
+/// Days that can show their state as an image
+pub trait Render: Solution {
+    fn render(&self) -> Picture;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl Format {
+    /// Picks the format matching the extension of the path
+    pub fn of_path(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()? {
+            "ppm" => Some(Format::Ppm),
+            "pgm" => Some(Format::Pgm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Repeats every pixel of the rows in a square of the given size
+fn scaled<T: Copy>(grid: &Grid<T>, scale: usize) -> impl Iterator<Item = T> + '_ {
+    grid.rows().flat_map(move |row| {
+        (0..scale).flat_map(move |_| {
+            row.iter()
+                .flat_map(move |&pixel| std::iter::repeat_n(pixel, scale))
+        })
+    })
+}
+
+/// Binary PPM (P6), with each cell drawn as a square of `scale` pixels
+pub fn ppm(picture: &Picture, scale: usize) -> Vec<u8> {
+    let colors = picture.colors();
+    let mut bytes = format!(
+        "P6\n{} {}\n255\n",
+        colors.width() * scale,
+        colors.height() * scale
+    )
+    .into_bytes();
+    bytes.extend(scaled(&colors, scale).flat_map(|color| [color.r, color.g, color.b]));
+
+    bytes
+}
+
+/// Binary PGM (P5), with each cell drawn as a square of `scale` pixels
+pub fn pgm(picture: &Picture, scale: usize) -> Vec<u8> {
+    let levels = picture.levels();
+    let mut bytes = format!(
+        "P5\n{} {}\n255\n",
+        levels.width() * scale,
+        levels.height() * scale
+    )
+    .into_bytes();
+    bytes.extend(scaled(&levels, scale));
+
+    bytes
+}
+
+/// SVG with each cell drawn as a square of `scale` pixels. Horizontal runs of the same colour are
+/// merged into a single rectangle to keep the file small.
+pub fn svg(picture: &Picture, scale: usize) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        picture.width() * scale,
+        picture.height() * scale,
+        picture.width(),
+        picture.height()
+    );
+
+    for (y, row) in picture.colors().rows().enumerate() {
+        let mut x = 0;
+        for run in row.chunk_by(|a, b| a == b) {
+            let Rgb { r, g, b } = run[0];
+            writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                x,
+                y,
+                run.len(),
+                r,
+                g,
+                b
+            )
+            .unwrap();
+            x += run.len();
+        }
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+pub fn encode(picture: &Picture, format: Format, scale: usize) -> Vec<u8> {
+    match format {
+        Format::Ppm => ppm(picture, scale),
+        Format::Pgm => pgm(picture, scale),
+        Format::Svg => svg(picture, scale).into_bytes(),
+    }
+}
+
+/// Renders the given day's state to an image file, in the format matching its extension
+pub fn run(number: u8, path: &str, scale: usize, input: &Input) -> ExitCode {
+    let Some((day, render)) = days::get(number).and_then(|day| Some((day, day.render?))) else {
+        eprintln!("error: Day {} has no image", number);
+        return ExitCode::FAILURE;
+    };
+    let Some(format) = Format::of_path(path) else {
+        eprintln!(
+            "error: Unknown image format of {}, expected .ppm, .pgm or .svg",
+            path
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let result = runner::load_with(&day, input, render)
+        .map_err(|error| error.to_string())
+        .and_then(|picture| {
+            fs::write(path, encode(&picture, format, scale))
+                .map_err(|error| format!("Failed to write {}: {}", path, error))
+        });
+
+    match result {
+        Ok(()) => {
+            println!("Wrote {}", path);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        Picture::Color(Grid::new(
+            3,
+            2,
+            vec![
+                Rgb::WHITE,
+                Rgb::WHITE,
+                Rgb::BLACK,
+                Rgb::new(255, 0, 0),
+                Rgb::BLACK,
+                Rgb::BLACK,
+            ],
+        ))
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut expected = b"P6\n3 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 255, 255, 255, 0, 0, 0]);
+        expected.extend([255, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        assert_eq!(expected, ppm(&picture(), 1));
+        assert_eq!(
+            b"P6\n6 4\n255\n".len() + 6 * 4 * 3,
+            ppm(&picture(), 2).len()
+        );
+    }
+
+    #[test]
+    fn test_pgm() {
+        let mut expected = b"P5\n4 2\n255\n".to_vec();
+        expected.extend([0, 0, 9, 9, 0, 0, 9, 9]);
+
+        assert_eq!(
+            expected,
+            pgm(&Picture::Gray(Grid::new(2, 1, vec![0, 9])), 2)
+        );
+        assert_eq!(
+            b"P5\n3 2\n255\n\xff\xff\x00\x4c\x00\x00".to_vec(),
+            pgm(&picture(), 1)
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 3 2\" shape-rendering=\"crispEdges\">\n\
+             <rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#ffffff\"/>\n\
+             <rect x=\"2\" y=\"0\" width=\"1\" height=\"1\" fill=\"#000000\"/>\n\
+             <rect x=\"0\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ff0000\"/>\n\
+             <rect x=\"1\" y=\"1\" width=\"2\" height=\"1\" fill=\"#000000\"/>\n\
+             </svg>\n",
+            svg(&picture(), 10)
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Some(Format::Svg), Format::of_path("out/tree.svg"));
+        assert_eq!(Some(Format::Pgm), Format::of_path("heights.pgm"));
+        assert_eq!(None, Format::of_path("tree.png"));
+        assert_eq!(None, Format::of_path("tree"));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::http::{self, Client, Request};
+use crate::runner::Selection;
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+pub const BASE_URL: &str = "https://adventofcode.com/2024";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const SESSION_FILE: &str = ".session";
+pub const USER_AGENT: &str = "aoc24 input downloader (std only)";
+/// Minimum time between two requests to the puzzle website
+pub const INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug)]
+pub enum Error {
+    Session,
+    Http(http::Error),
+    Status { day: u8, status: u16, body: String },
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Session => write!(
+                f,
+                "No session token, set {} or write it to {}",
+                SESSION_VAR, SESSION_FILE
+            ),
+            Error::Http(error) => write!(f, "Request failed: {}", error),
+            Error::Status { day, status, body } => write!(
+                f,
+                "Fetching input of day {} failed with status {}: {}",
+                day,
+                status,
+                body.trim()
+            ),
+            Error::Io { path, error } => write!(f, "Failed to write {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Session token of the puzzle website, taken from the environment or else the session file
+pub fn session() -> Result<String, Error> {
+    env::var(SESSION_VAR)
+        .ok()
+        .or_else(|| fs::read_to_string(SESSION_FILE).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or(Error::Session)
+}
+
+/// Spaces out consecutive calls by at least the given interval
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    last: Cell<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Cell::new(None),
+        }
+    }
+
+    pub fn wait(&self) {
+        if let Some(last) = self.last.get() {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+
+        self.last.set(Some(Instant::now()));
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads puzzle inputs into a directory, never downloading an input that is already there
+pub struct Downloader<C: Client> {
+    client: C,
+    base_url: String,
+    session: String,
+    directory: PathBuf,
+    throttle: Throttle,
+}
+
+impl<C: Client> Downloader<C> {
+    pub fn new(
+        client: C,
+        base_url: &str,
+        session: &str,
+        directory: &Path,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            client,
+            base_url: base_url.to_string(),
+            session: session.to_string(),
+            directory: directory.to_path_buf(),
+            throttle: Throttle::new(interval),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.directory.join(format!("day{}.txt", day))
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<Fetched, Error> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        self.throttle.wait();
+        let request = Request::get(&format!("{}/day/{}/input", self.base_url, day))
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT);
+        let response = self.client.send(&request).map_err(Error::Http)?;
+
+        if response.status != 200 {
+            return Err(Error::Status {
+                day,
+                status: response.status,
+                body: response.body,
+            });
+        }
+
+        // Write to a temporary file first so that an interrupted write doesn't count as cached
+        let io_error = |error| Error::Io {
+            path: path.clone(),
+            error,
+        };
+        let partial = path.with_extension("txt.partial");
+        fs::create_dir_all(&self.directory).map_err(io_error)?;
+        fs::write(&partial, response.body).map_err(io_error)?;
+        fs::rename(&partial, &path).map_err(io_error)?;
+
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Downloads the inputs of the selected days into the `input` directory
+pub fn run(selection: Selection) -> ExitCode {
+    let session = match session() {
+        Ok(session) => session,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let downloader = Downloader::new(
+        http::client_for(BASE_URL),
+        BASE_URL,
+        &session,
+        Path::new("input"),
+        INTERVAL,
+    );
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in selection.days() {
+        match downloader.fetch(day.number) {
+            Ok(Fetched::Cached(path)) => println!("Day {}: {} exists", day.number, path.display()),
+            Ok(Fetched::Downloaded(path)) => {
+                println!("Day {}: downloaded {}", day.number, path.display())
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tests::serve;
+    use crate::http::TcpClient;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc24-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, requests) = serve(vec![(200, "1 2\n3 4\n"), (404, "Not found")]);
+        let directory = temp_dir("fetch");
+        let downloader = Downloader::new(TcpClient, &url, "secret", &directory, Duration::ZERO);
+
+        assert_eq!(
+            Ok(Fetched::Downloaded(directory.join("day1.txt"))),
+            downloader.fetch(1).map_err(|error| error.to_string())
+        );
+        assert_eq!(
+            "1 2\n3 4\n",
+            fs::read_to_string(downloader.path(1)).unwrap()
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=secret\r\n"));
+
+        // Cached inputs are not requested again, so the next request is for day 2
+        assert_eq!(
+            Ok(Fetched::Cached(directory.join("day1.txt"))),
+            downloader.fetch(1).map_err(|error| error.to_string())
+        );
+        assert!(matches!(
+            downloader.fetch(2),
+            Err(Error::Status { status: 404, .. })
+        ));
+        assert!(requests.recv().unwrap().starts_with("GET /day/2/input "));
+        assert!(!downloader.path(2).exists());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let throttle = Throttle::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        throttle.wait();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        throttle.wait();
+        throttle.wait();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::parse::ParseError;
+use std::fmt::{Display, Formatter, Write};
+
+/// Minimal JSON document model for the files written by the tooling. Objects keep the order of
+/// their keys so written files stay stable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn parse(string: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser { string, offset: 0 };
+        let value = parser.value()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.expected("end of input")),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, Value)] {
+        match self {
+            Value::Object(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Writes the value with nested arrays and objects indented by two spaces per level
+    pub fn pretty(&self) -> String {
+        let mut string = String::new();
+        self.write_pretty(&mut string, 0);
+        string
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize) {
+        let indent = |depth: usize| "  ".repeat(depth);
+
+        match self {
+            Value::Array(values) if !values.is_empty() => {
+                out.push_str("[\n");
+                for (i, value) in values.iter().enumerate() {
+                    out.push_str(&indent(depth + 1));
+                    value.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&indent(depth));
+                out.push(']');
+            }
+            Value::Object(entries) if !entries.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    let _ = write!(out, "{}{}: ", indent(depth + 1), Value::String(key.clone()));
+                    value.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&indent(depth));
+                out.push('}');
+            }
+            value => {
+                let _ = write!(out, "{}", value);
+            }
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(bool) => write!(f, "{}", bool),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::String(string) => {
+                f.write_char('"')?;
+                for char in string.chars() {
+                    match char {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        char if (char as u32) < 0x20 => write!(f, "\\u{:04x}", char as u32)?,
+                        char => f.write_char(char)?,
+                    }
+                }
+                f.write_char('"')
+            }
+            Value::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            Value::Object(entries) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}:{}", Value::String(key.clone()), value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    string: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.string[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.offset += char.len_utf8();
+        Some(char)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expected(&self, expected: &str) -> ParseError {
+        let before = &self.string[..self.offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        let found = match self.peek() {
+            Some(char) => format!("'{}'", char),
+            None => "end of input".to_string(),
+        };
+
+        ParseError::new(
+            line,
+            column,
+            format!("expected {}, found {}", expected, found),
+        )
+    }
+
+    fn expect(&mut self, char: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() == Some(char) {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => Err(self.expected(&format!("'{}'", char))),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, ParseError> {
+        match self.string[self.offset..].starts_with(keyword) {
+            true => {
+                self.offset += keyword.len();
+                Ok(value)
+            }
+            false => Err(self.expected("a value")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('n') => self.keyword("null", Value::Null),
+            Some('t') => self.keyword("true", Value::Bool(true)),
+            Some('f') => self.keyword("false", Value::Bool(false)),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(char) if char == '-' || char.is_ascii_digit() => self.number(),
+            _ => Err(self.expected("a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.offset;
+        while self
+            .peek()
+            .is_some_and(|char| char.is_ascii_digit() || "+-.eE".contains(char))
+        {
+            self.next();
+        }
+
+        match self.string[start..self.offset].parse() {
+            Ok(number) => Ok(Value::Number(number)),
+            Err(_) => {
+                self.offset = start;
+                Err(self.expected("a number"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let hex = self.string.get(self.offset..self.offset + 4);
+                        match hex
+                            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                            .and_then(char::from_u32)
+                        {
+                            Some(char) => {
+                                self.offset += 4;
+                                string.push(char);
+                            }
+                            None => return Err(self.expected("a unicode escape")),
+                        }
+                    }
+                    Some(char @ ('"' | '\\' | '/')) => string.push(char),
+                    _ => return Err(self.expected("an escape sequence")),
+                },
+                Some(char) => string.push(char),
+                None => return Err(self.expected("'\"'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        self.expect('[')?;
+        let mut values = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some(']') => {
+                    self.next();
+                    return Ok(Value::Array(values));
+                }
+                _ => return Err(self.expected("',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.expect('{')?;
+        let mut entries = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(Value::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some('}') => {
+                    self.next();
+                    return Ok(Value::Object(entries));
+                }
+                _ => return Err(self.expected("',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let string = r#"{"a":[1,2.5,-3],"b":{"c":"x\"y\n"},"d":true,"e":null,"f":[],"g":{}}"#;
+        let value = Value::parse(string).unwrap();
+
+        assert_eq!(string, value.to_string());
+        assert_eq!(value, Value::parse(&value.pretty()).unwrap());
+        assert_eq!(
+            Some(2.5),
+            value.get("a").unwrap().as_array().unwrap()[1].as_f64()
+        );
+        assert_eq!(
+            Some("x\"y\n"),
+            value.get("b").unwrap().get("c").unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn test_pretty() {
+        let value = Value::parse(r#"{"a": [1, 2], "b": {}}"#).unwrap();
+
+        assert_eq!(
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}",
+            value.pretty()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 8, "expected ':', found '\"'")),
+            Value::parse("{\"a\": 1\n  ,\"b\" \"c\"}")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 4, "expected ',' or ']', found '2'")),
+            Value::parse("[1 2]")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                2,
+                "expected a value, found end of input"
+            )),
+            Value::parse("[")
+        );
+    }
+}
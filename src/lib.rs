@@ -0,0 +1,33 @@
+pub mod allocations;
+pub mod animate;
+pub mod answer;
+pub mod answers;
+pub mod api;
+pub mod bench;
+pub mod cache;
+pub mod cli;
+pub mod config;
+pub mod days;
+pub mod examples;
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod http;
+pub mod image;
+pub mod inputs;
+pub mod json;
+pub mod metrics;
+pub mod parse;
+pub mod pool;
+#[cfg(test)]
+pub mod property;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod status;
+pub mod submit;
+pub mod trace;
+
+pub use answer::Answer;
+pub use api::{solve, solve_with, Error, Options};
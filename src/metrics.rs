@@ -0,0 +1,193 @@
+//! Named counters and timers for finding out why a part is slow, like how many states a search
+//! visited. They are only recorded when built with the `metrics` feature; otherwise recording
+//! compiles to nothing.
+//!
+//! Metrics are kept per thread, so that parts solved side by side don't mix theirs up.
+
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Whether metrics are recorded in this build
+pub const ENABLED: bool = cfg!(feature = "metrics");
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Metric {
+    Count(u64),
+    /// A number of bytes, shown in binary units
+    Bytes(u64),
+    Time {
+        total: Duration,
+        calls: u64,
+    },
+}
+
+#[cfg(feature = "metrics")]
+impl Metric {
+    fn merge(&mut self, other: Metric) {
+        match (self, other) {
+            (Metric::Count(count), Metric::Count(more))
+            | (Metric::Bytes(count), Metric::Bytes(more)) => *count += more,
+            (
+                Metric::Time { total, calls },
+                Metric::Time {
+                    total: more,
+                    calls: more_calls,
+                },
+            ) => {
+                *total += more;
+                *calls += more_calls;
+            }
+            (metric, other) => *metric = other,
+        }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Count(count) => write!(f, "{}", count),
+            Metric::Bytes(bytes) if *bytes < 1024 => write!(f, "{} B", bytes),
+            Metric::Bytes(bytes) => {
+                let units = ["KiB", "MiB", "GiB"];
+                let mut size = *bytes as f64 / 1024.0;
+                let mut unit = 0;
+                while size >= 1024.0 && unit + 1 < units.len() {
+                    size /= 1024.0;
+                    unit += 1;
+                }
+                write!(f, "{:.2} {}", size, units[unit])
+            }
+            Metric::Time { total, calls } => write!(f, "{:.2?} in {} calls", total, calls),
+        }
+    }
+}
+
+pub type Metrics = Vec<(&'static str, Metric)>;
+
+#[cfg(feature = "metrics")]
+thread_local! {
+    static RECORDED: std::cell::RefCell<Metrics> = const { std::cell::RefCell::new(vec![]) };
+}
+
+#[cfg(feature = "metrics")]
+fn record(name: &'static str, metric: Metric) {
+    RECORDED.with_borrow_mut(|recorded| {
+        match recorded.iter_mut().find(|(recorded, _)| *recorded == name) {
+            Some((_, recorded)) => recorded.merge(metric),
+            None => recorded.push((name, metric)),
+        }
+    });
+}
+
+/// Increases the named counter by one
+#[inline(always)]
+pub fn count(name: &'static str) {
+    add(name, 1);
+}
+
+/// Increases the named counter by the given amount
+#[inline(always)]
+pub fn add(name: &'static str, amount: u64) {
+    #[cfg(feature = "metrics")]
+    record(name, Metric::Count(amount));
+    #[cfg(not(feature = "metrics"))]
+    let _ = (name, amount);
+}
+
+/// Runs the closure, adding the time it took to the named timer
+#[inline(always)]
+pub fn time<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "metrics")]
+    {
+        let start = std::time::Instant::now();
+        let result = f();
+        record(
+            name,
+            Metric::Time {
+                total: start.elapsed(),
+                calls: 1,
+            },
+        );
+        result
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = name;
+        f()
+    }
+}
+
+/// Returns the metrics recorded on this thread in order of first use, and starts over
+pub fn take() -> Metrics {
+    #[cfg(feature = "metrics")]
+    return RECORDED.take();
+    #[cfg(not(feature = "metrics"))]
+    vec![]
+}
+
+/// Formats the metrics as aligned lines, each indented by two spaces
+pub fn report(metrics: &Metrics) -> String {
+    let width = metrics
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or_default();
+
+    metrics
+        .iter()
+        .map(|(name, metric)| format!("  {:<width$}  {}\n", name, metric))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let metrics = vec![
+            ("day16.popped", Metric::Count(4021)),
+            (
+                "day6.loop_check",
+                Metric::Time {
+                    total: Duration::from_millis(12),
+                    calls: 5,
+                },
+            ),
+        ];
+
+        assert_eq!(
+            "  day16.popped     4021\n  day6.loop_check  12.00ms in 5 calls\n",
+            report(&metrics)
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!("512 B", Metric::Bytes(512).to_string());
+        assert_eq!("1.50 KiB", Metric::Bytes(1536).to_string());
+        assert_eq!("3.00 MiB", Metric::Bytes(3 << 20).to_string());
+        assert_eq!("2048.00 GiB", Metric::Bytes(2 << 40).to_string());
+    }
+
+    #[test]
+    fn test_record() {
+        take();
+        count("calls");
+        add("calls", 2);
+        assert_eq!(5, time("work", || 5));
+
+        match ENABLED {
+            true => {
+                let metrics = take();
+                assert_eq!(("calls", Metric::Count(3)), metrics[0]);
+                assert!(matches!(
+                    metrics[1],
+                    ("work", Metric::Time { calls: 1, .. })
+                ));
+            }
+            false => assert!(take().is_empty()),
+        }
+        assert!(take().is_empty());
+    }
+}
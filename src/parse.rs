@@ -0,0 +1,376 @@
+use crate::grid::Grid;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::{FromStr, SplitWhitespace};
+
+/// Describes where and why puzzle input could not be parsed. Lines and columns start at 1, or are
+/// 0 for errors in the parameters rather than in the input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error for something that should have been present somewhere in the input, positioned at
+    /// the end of the input
+    pub fn missing(input: &str, expected: &str) -> Self {
+        let line = input.lines().count().max(1);
+        let column = input.lines().last().map_or(0, |text| text.chars().count()) + 1;
+
+        Self::new(
+            line,
+            column,
+            format!("expected {}, found end of input", expected),
+        )
+    }
+
+    /// Error for a parameter the input is parsed with, which has no position
+    pub fn parameter(message: impl Into<String>) -> Self {
+        Self::new(0, 0, message)
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    /// Quotes the offending line of the input with a marker below the column of the error
+    pub fn excerpt(&self, input: &str) -> Option<String> {
+        let text = input.lines().nth(self.line.checked_sub(1)?)?;
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        Some(format!(
+            "{} |\n{} | {}\n{} | {}^",
+            gutter,
+            self.line,
+            text,
+            gutter,
+            " ".repeat(self.column.saturating_sub(1))
+        ))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+
+        if self.line == 0 {
+            return f.write_str(&self.message);
+        }
+
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input along with its line number, used to position parse errors
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Self { number, text }
+    }
+
+    /// Column of a slice of this line, or of the end of the line if the slice is not part of it
+    pub fn column_of(&self, slice: &str) -> usize {
+        let offset = (slice.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+
+        match self.text.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            _ => self.end_column(),
+        }
+    }
+
+    pub fn end_column(&self) -> usize {
+        self.text.chars().count() + 1
+    }
+
+    /// Error pointing at the given slice of this line
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column_of(at), message)
+    }
+
+    pub fn expected(&self, at: &str, expected: &str) -> ParseError {
+        let found = match at.is_empty() {
+            true => "nothing".to_string(),
+            false => format!("'{}'", at),
+        };
+
+        self.error(at, format!("expected {}, found {}", expected, found))
+    }
+
+    pub fn expected_at_end(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            self.number,
+            self.end_column(),
+            format!("expected {}, found end of line", expected),
+        )
+    }
+
+    /// Parses a slice of this line, describing what was expected when that fails
+    pub fn parse<T: FromStr>(&self, field: &str, expected: &str) -> Result<T, ParseError> {
+        field.parse().map_err(|_| self.expected(field, expected))
+    }
+
+    pub fn split_once(
+        &self,
+        text: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        text.split_once(delimiter)
+            .ok_or_else(|| self.expected_at_end(&format!("'{}'", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.expected(text, &format!("'{}'", prefix)))
+    }
+
+    /// Whitespace separated fields of this line
+    pub fn fields(&self) -> Fields<'a> {
+        Fields {
+            line: *self,
+            fields: self.text.split_whitespace(),
+        }
+    }
+}
+
+/// Iterator over the whitespace separated fields of a line that reports missing fields
+pub struct Fields<'a> {
+    line: Line<'a>,
+    fields: SplitWhitespace<'a>,
+}
+
+impl Fields<'_> {
+    pub fn parse<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        match self.fields.next() {
+            Some(field) => self.line.parse(field, expected),
+            None => Err(self.line.expected_at_end(expected)),
+        }
+    }
+}
+
+/// Numbered lines of the input
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(index + 1, text))
+}
+
+/// Groups of lines separated by blank lines, keeping their original line numbers
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    lines(input)
+        .fold(vec![], |mut sections: Vec<Vec<Line>>, line| {
+            match (line.text.trim().is_empty(), sections.last_mut()) {
+                (true, _) => sections.push(vec![]),
+                (false, Some(section)) => section.push(line),
+                (false, None) => sections.push(vec![line]),
+            }
+
+            sections
+        })
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+/// Splits the input into exactly two sections separated by blank lines
+pub fn two_sections<'a>(
+    input: &'a str,
+    expected: &str,
+) -> Result<(Vec<Line<'a>>, Vec<Line<'a>>), ParseError> {
+    let mut sections = sections(input).into_iter();
+
+    match (sections.next(), sections.next(), sections.next()) {
+        (Some(first), Some(second), None) => Ok((first, second)),
+        (_, _, Some(third)) => Err(third[0].expected(third[0].text, "end of input")),
+        _ => Err(ParseError::missing(input, expected)),
+    }
+}
+
+/// Parses the lines of the input into a grid, mapping each character to a cell value. Every row
+/// must be as long as the first one.
+pub fn grid<T>(input: &str, map: impl FnMut(char) -> T) -> Result<Grid<T>, ParseError> {
+    let lines: Vec<Line> = lines(input).collect();
+    match lines.first() {
+        Some(line) if !line.text.is_empty() => grid_lines(&lines, map),
+        _ => Err(ParseError::missing(input, "a grid")),
+    }
+}
+
+/// Parses the given lines into a grid like [`grid`], for grids that are one section of the input
+pub fn grid_lines<T>(
+    lines: &[Line],
+    mut map: impl FnMut(char) -> T,
+) -> Result<Grid<T>, ParseError> {
+    let width = lines.first().map_or(0, |line| line.text.chars().count());
+    let mut cells = vec![];
+    let mut height = 0;
+    for line in lines {
+        let mut chars = line.text.char_indices();
+        cells.extend(chars.by_ref().take(width).map(|(_, char)| map(char)));
+
+        if let Some((offset, _)) = chars.next() {
+            let expected = format!("the end of the row after {} cells", width);
+            return Err(line.expected(&line.text[offset..], &expected));
+        }
+        if cells.len() < (height + 1) * width {
+            return Err(line.expected_at_end(&format!("{} cells like the first row", width)));
+        }
+        height += 1;
+    }
+
+    Ok(Grid::new(width, height, cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_field() {
+        let line = Line::new(3, "12 ab 7");
+        let mut fields = line.fields();
+
+        assert_eq!(Ok(12), fields.parse::<u32>("a number"));
+        assert_eq!(
+            Err(ParseError::new(3, 4, "expected a number, found 'ab'")),
+            fields.parse::<u32>("a number")
+        );
+        assert_eq!(Ok(7), fields.parse::<u32>("a number"));
+        assert_eq!(
+            Err(ParseError::new(
+                3,
+                8,
+                "expected a number, found end of line"
+            )),
+            fields.parse::<u32>("a number")
+        );
+    }
+
+    #[test]
+    fn test_split_and_strip() {
+        let line = Line::new(1, "p=1,2");
+
+        assert_eq!(Ok("1,2"), line.strip_prefix(line.text, "p="));
+        assert_eq!(
+            Err(ParseError::new(1, 1, "expected 'v=', found 'p=1,2'")),
+            line.strip_prefix(line.text, "v=")
+        );
+        assert_eq!(Ok(("p=1", "2")), line.split_once(line.text, ","));
+        assert_eq!(
+            Err(ParseError::new(1, 6, "expected '|', found end of line")),
+            line.split_once(line.text, "|")
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let sections = sections("a\nb\n\nc\n\n\nd\n");
+
+        assert_eq!(3, sections.len());
+        assert_eq!(vec![Line::new(1, "a"), Line::new(2, "b")], sections[0]);
+        assert_eq!(vec![Line::new(4, "c")], sections[1]);
+        assert_eq!(vec![Line::new(7, "d")], sections[2]);
+    }
+
+    #[test]
+    fn test_two_sections() {
+        assert!(two_sections("a\n\nb", "two sections").is_ok());
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                2,
+                "expected two sections, found end of input"
+            )),
+            two_sections("a", "two sections")
+        );
+        assert_eq!(
+            Err(ParseError::new(5, 1, "expected end of input, found 'c'")),
+            two_sections("a\n\nb\n\nc", "two sections")
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        assert_eq!(
+            ParseError::new(2, 4, "expected a start, found end of input"),
+            ParseError::missing("abcd\nefg", "a start")
+        );
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let error = ParseError::new(2, 4, "expected a number, found 'x'");
+
+        assert_eq!(
+            Some("  |\n2 | 12 x\n  |    ^".to_string()),
+            error.excerpt("1 2\n12 x")
+        );
+        assert_eq!(None, error.excerpt("1 2"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "day 7, line 2, column 5: expected a number, found 'x'",
+            ParseError::new(2, 5, "expected a number, found 'x'")
+                .in_day(7)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let parsed = grid("ab\ncd\n", |char| char).unwrap();
+
+        assert_eq!((2, 2), (parsed.width(), parsed.height()));
+        assert_eq!(Some(&'c'), parsed.get(0, 1));
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                3,
+                "expected the end of the row after 2 cells, found 'e'"
+            )),
+            grid("ab\ncde", |char| char).map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                2,
+                "expected 2 cells like the first row, found end of line"
+            )),
+            grid("ab\nc\nde", |char| char).map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "expected a grid, found end of input")),
+            grid("", |char| char).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Fixed size pool of worker threads that isolates panics per job
+
+use std::any::Any;
+use std::num::NonZero;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Outcome of a single job
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completed<R> {
+    /// The job's result, or the message it panicked with
+    pub result: Result<R, String>,
+    pub duration: Duration,
+}
+
+/// Number of workers to use when not limited, one per available core
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, NonZero::get)
+}
+
+/// Runs the jobs on the given number of worker threads, returning their outcomes in the order of
+/// the jobs. A panicking job doesn't affect the others.
+pub fn run<T: Send, R: Send>(
+    jobs: Vec<T>,
+    workers: usize,
+    job: impl Fn(T) -> R + Sync,
+) -> Vec<Completed<R>> {
+    let count = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            let sender = sender.clone();
+            let (queue, job) = (&queue, &job);
+
+            scope.spawn(move || loop {
+                // Take the next job without holding on to the lock while running it
+                let next = queue.lock().unwrap().next();
+                let Some((index, input)) = next else {
+                    break;
+                };
+
+                let start = Instant::now();
+                let result = panic::catch_unwind(AssertUnwindSafe(|| job(input)));
+                let duration = start.elapsed();
+
+                let result = result.map_err(panic_message);
+                sender
+                    .send((index, Completed { result, duration }))
+                    .unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut completed: Vec<_> = receiver.into_iter().collect();
+    completed.sort_by_key(|(index, _)| *index);
+    completed
+        .into_iter()
+        .map(|(_, completed)| completed)
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let completed = run((1..=20).collect(), 4, |n: u64| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+
+        assert_eq!(
+            (1..=20).map(|n| Ok(n * n)).collect::<Vec<_>>(),
+            completed
+                .into_iter()
+                .map(|completed| completed.result)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_run_panics() {
+        let completed = run(vec![1, 0, 2], 2, |n: u32| match n {
+            0 => panic!("division by {}", n),
+            n => 10 / n,
+        });
+
+        assert_eq!(Ok(10), completed[0].result);
+        assert_eq!(Err("division by 0".to_string()), completed[1].result);
+        assert_eq!(Ok(5), completed[2].result);
+        assert!(run(vec![], 0, |n: u32| n).is_empty());
+    }
+}
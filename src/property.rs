@@ -0,0 +1,216 @@
+//! Minimal property based testing: random inputs from a seeded generator, checked against a
+//! property and shrunk to a smaller failing input when the property doesn't hold.
+
+use std::env;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// Number of generated inputs per property, overridable with `PROPERTY_CASES`
+pub const CASES: usize = 1000;
+/// Seed of the generator, overridable with `PROPERTY_SEED` to explore other inputs
+pub const SEED: u64 = 0x5eed_a0c2_4000_0001;
+
+/// Small deterministic xorshift generator, so that failures can be reproduced from their seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % span) as i64
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn vec<T>(
+        &mut self,
+        len: RangeInclusive<usize>,
+        mut element: impl FnMut(&mut Rng) -> T,
+    ) -> Vec<T> {
+        let len = self.range(*len.start() as i64..=*len.end() as i64) as usize;
+        (0..len).map(|_| element(self)).collect()
+    }
+}
+
+/// Values that can be made smaller while staying of the same shape
+pub trait Shrink: Sized {
+    /// Candidates that are simpler than this value, simplest first
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink {
+    ($($t:ty),*) => {
+        $(impl Shrink for $t {
+            #[allow(unused_comparisons)]
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                if *self != 0 {
+                    candidates.push(0);
+                    candidates.push(*self / 2);
+                    candidates.push(if *self > 0 { *self - 1 } else { *self + 1 });
+                }
+                candidates.dedup();
+                candidates.retain(|candidate| candidate != self);
+                candidates
+            }
+        })*
+    };
+}
+
+impl_shrink!(u8, u32, u64, usize, i32, i64);
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        if self.len() > 1 {
+            candidates.push(self[..self.len() / 2].to_vec());
+            candidates.push(self[self.len() / 2..].to_vec());
+        }
+
+        for i in 0..self.len() {
+            let mut without = self.clone();
+            without.remove(i);
+            candidates.push(without);
+        }
+
+        for (i, element) in self.iter().enumerate() {
+            for smaller in element.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = smaller;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let first = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let second = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+
+        first.chain(second).collect()
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone, C: Shrink + Clone> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        ((self.0.clone(), self.1.clone()), self.2.clone())
+            .shrink()
+            .into_iter()
+            .map(|((a, b), c)| (a, b, c))
+            .collect()
+    }
+}
+
+/// Compares an answer with the one of a reference implementation
+pub fn equal<T: PartialEq + Debug>(actual: T, expected: T) -> Result<(), String> {
+    match actual == expected {
+        true => Ok(()),
+        false => Err(format!("expected {:?}, got {:?}", expected, actual)),
+    }
+}
+
+/// Checks the property against generated inputs, panicking with the smallest failing input found
+pub fn check<T: Shrink + Clone + Debug>(
+    generate: impl Fn(&mut Rng) -> T,
+    property: impl Fn(&T) -> Result<(), String>,
+) {
+    let seed = env::var("PROPERTY_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(SEED);
+    let cases = env::var("PROPERTY_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(CASES);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let input = generate(&mut rng);
+
+        if let Err(error) = property(&input) {
+            let (input, error) = minimize(input, error, &property);
+            panic!(
+                "property failed on case {} of seed {}: {}\nshrunk input: {:?}",
+                case, seed, error, input
+            );
+        }
+    }
+}
+
+/// Greedily replaces the failing input by simpler candidates that still fail
+fn minimize<T: Shrink>(
+    mut input: T,
+    mut error: String,
+    property: impl Fn(&T) -> Result<(), String>,
+) -> (T, String) {
+    'shrinking: loop {
+        for candidate in input.shrink() {
+            if let Err(candidate_error) = property(&candidate) {
+                input = candidate;
+                error = candidate_error;
+                continue 'shrinking;
+            }
+        }
+
+        return (input, error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(7);
+
+        assert!((0..1000).all(|_| (-3..=5).contains(&rng.range(-3..=5))));
+        assert!((0..1000).any(|_| rng.range(-3..=5) == -3));
+        assert!((0..1000).any(|_| rng.range(-3..=5) == 5));
+    }
+
+    #[test]
+    fn test_minimize() {
+        // Fails for every list containing a number of at least 10, which shrinks to just [10]
+        let property = |list: &Vec<u32>| match list.iter().any(|&n| n >= 10) {
+            true => Err("too large".to_string()),
+            false => Ok(()),
+        };
+
+        let (input, _) = minimize(vec![3, 25, 7, 40, 1], "".to_string(), property);
+
+        assert_eq!(vec![10], input);
+    }
+
+    #[test]
+    #[should_panic(expected = "shrunk input: [10]")]
+    fn test_check_failure() {
+        check(
+            |rng| rng.vec(0..=10, |rng| rng.range(0..=100) as u32),
+            |list| equal(list.iter().all(|&n| n < 10), true),
+        );
+    }
+}
@@ -0,0 +1,566 @@
+use crate::allocations;
+use crate::answer::Answer;
+use crate::answers::{Answers, Verdict};
+use crate::cache::{Cache, Key};
+use crate::config::Config;
+use crate::metrics::{self, Metrics};
+use crate::parse::ParseError;
+use crate::solution::{Day, Params, Solver};
+use crate::{animate, cli, days, pool, trace};
+use std::fmt::{Display, Formatter};
+use std::panic;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+/// Which days to run
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Selection {
+    Day(u8),
+    All,
+}
+
+impl Selection {
+    pub fn days(&self) -> Vec<Day> {
+        match self {
+            Selection::Day(number) => days::get(*number).into_iter().collect(),
+            Selection::All => days::registry(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Input {
+        path: String,
+        error: io::Error,
+    },
+    Parse {
+        path: String,
+        input: String,
+        error: ParseError,
+    },
+    /// A solved part found no answer for the input
+    NoAnswer {
+        path: String,
+        part: u8,
+    },
+    /// The requested part has no solution yet
+    Unsolved {
+        day: u8,
+        part: u8,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Input { path, error } => write!(f, "Failed to read {}: {}", path, error),
+            Error::Parse { path, input, error } => {
+                write!(f, "Failed to parse {}: {}", path, error)?;
+
+                match error.excerpt(input) {
+                    Some(excerpt) => write!(f, "\n{}", excerpt),
+                    None => Ok(()),
+                }
+            }
+            Error::NoAnswer { path, part } => {
+                write!(f, "Found no answer to part {} for {}", part, path)
+            }
+            Error::Unsolved { day, part } => {
+                write!(f, "Part {} of day {} is not solved", part, day)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn input_path(day: u8) -> String {
+    format!("input/day{}.txt", day)
+}
+
+/// Where the puzzle input is read from
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub enum Source {
+    /// The day's input file in the `input` directory
+    #[default]
+    Default,
+    Path(String),
+    Stdin,
+    /// The example input from the puzzle description
+    Sample,
+}
+
+impl Source {
+    /// Interprets an input path argument, where `-` means stdin
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(path.to_string()),
+        }
+    }
+
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            Source::Default => input_path(day),
+            Source::Path(path) => path.clone(),
+            Source::Stdin => "stdin".to_string(),
+            Source::Sample => format!("sample of day {}", day),
+        }
+    }
+
+    pub fn read(&self, day: &Day) -> Result<String, Error> {
+        let result = match self {
+            Source::Default => fs::read_to_string(input_path(day.number)),
+            Source::Path(path) => fs::read_to_string(path),
+            Source::Stdin => io::read_to_string(io::stdin()),
+            Source::Sample => Ok(day.sample.to_string()),
+        };
+
+        result.map_err(|error| Error::Input {
+            path: self.name(day.number),
+            error,
+        })
+    }
+}
+
+/// The input to run days against
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Input {
+    pub source: Source,
+    /// Parameters of every day run, taking precedence over those in the config
+    pub params: Params,
+    /// Parameters of each day from the config file and `--set`
+    pub config: Config,
+}
+
+impl Input {
+    /// Parameters of the given day: those of its sample when running the sample, overridden by
+    /// those in the config and then by those given for every day
+    pub fn params(&self, day: &Day) -> Params {
+        let mut params = match self.source {
+            Source::Sample => Params::from(day.sample_params),
+            _ => Params::default(),
+        };
+        params.extend(&self.config.params(day.number));
+        params.extend(&self.params);
+        params
+    }
+
+    /// Reads the config file underneath the parameters that were already set
+    pub fn load_config(&mut self) -> Result<(), String> {
+        let mut config = Config::load()?;
+        config.extend(&self.config);
+        self.config = config;
+        Ok(())
+    }
+}
+
+/// Reads the input of the given day and parses it
+pub fn load(day: &Day, input: &Input) -> Result<Box<dyn Solver>, Error> {
+    load_with(day, input, day.parse)
+}
+
+/// Reads the input of the given day and parses it with the given parser
+pub fn load_with<T>(
+    day: &Day,
+    input: &Input,
+    parse: fn(&str, &Params) -> Result<T, ParseError>,
+) -> Result<T, Error> {
+    let string = input.source.read(day)?;
+
+    parse_input(day, input, string, parse)
+}
+
+/// Parses the given day's input that was already read from its source
+fn parse_input<T>(
+    day: &Day,
+    input: &Input,
+    string: String,
+    parse: fn(&str, &Params) -> Result<T, ParseError>,
+) -> Result<T, Error> {
+    parse(&string, &input.params(day)).map_err(|error| Error::Parse {
+        path: input.source.name(day.number),
+        input: string,
+        error,
+    })
+}
+
+/// Runs a stage of solving, returning the metrics it recorded along with its allocations
+fn recorded<R>(stage: impl FnOnce() -> R) -> (R, Metrics) {
+    // Workers are reused, so drop whatever an earlier stage left behind on this thread
+    metrics::take();
+    let (result, allocations) = allocations::measure(stage);
+    let mut recorded = metrics::take();
+    if allocations::ENABLED {
+        recorded.extend(allocations.metrics());
+    }
+
+    (result, recorded)
+}
+
+/// Cache to use unless disabled. Metrics and trace events are only recorded while solving, so
+/// asking for either bypasses the cache.
+fn open_cache(cache: bool, stats: bool) -> Option<Cache> {
+    match cache && !stats && !trace::active() {
+        true => Cache::open(),
+        false => None,
+    }
+}
+
+fn store(cache: &Cache, key: Key, answer: &Answer) {
+    if let Err(error) = cache.insert(key, answer) {
+        eprintln!("warning: Failed to cache the answer: {}", error);
+    }
+}
+
+/// Runs the given day against its input, printing the answers of the requested part(s) along
+/// with the metrics and allocations recorded while solving them if requested. Answers found in
+/// the cache are printed as such, and the input is only parsed if any part isn't cached.
+pub fn run(
+    day: &Day,
+    part: Option<u8>,
+    input: &Input,
+    stats: bool,
+    cache: Option<&Cache>,
+) -> Result<(), Error> {
+    if let Some(part) = part.filter(|&part| part > day.parts) {
+        return Err(Error::Unsolved {
+            day: day.number,
+            part,
+        });
+    }
+
+    let string = input.source.read(day)?;
+    let parts: Vec<(u8, Option<Key>, Option<Answer>)> = (1..=day.parts)
+        .filter(|&number| part.is_none_or(|part| part == number))
+        .map(|number| {
+            let key = cache.map(|cache| cache.key(day.number, number, &string, &input.params(day)));
+            (number, key, key.and_then(|key| cache?.get(key)))
+        })
+        .collect();
+
+    let solver = match parts.iter().all(|(_, _, cached)| cached.is_some()) {
+        true => None,
+        false => {
+            let (solver, parsed) = recorded(|| parse_input(day, input, string, day.parse));
+            if stats && !parsed.is_empty() {
+                print!("Parse\n{}", metrics::report(&parsed));
+            }
+            Some(solver?)
+        }
+    };
+
+    for (number, key, cached) in parts {
+        let (answer, solved, marker) = match (cached, &solver) {
+            (Some(answer), _) => (answer, vec![], " (cached)"),
+            (None, Some(solver)) => {
+                let (answer, solved) = recorded(|| match number {
+                    1 => Some(solver.part_1()),
+                    _ => solver.part_2(),
+                });
+                let Some(answer) = answer else {
+                    return Err(Error::NoAnswer {
+                        path: input.source.name(day.number),
+                        part: number,
+                    });
+                };
+                if let (Some(cache), Some(key)) = (cache, key) {
+                    store(cache, key, &answer);
+                }
+                (answer, solved, "")
+            }
+            (None, None) => continue,
+        };
+
+        println!("Part {}: {}{}", number, answer, marker);
+        if let Some(rendering) = answer.rendering() {
+            println!("{}", rendering);
+        }
+        if stats {
+            print!("{}", metrics::report(&solved));
+        }
+    }
+
+    Ok(())
+}
+
+/// Points out that `--stats` has nothing to show in builds without metrics or allocations
+fn check_stats(stats: bool) {
+    if stats && !metrics::ENABLED && !allocations::ENABLED {
+        eprintln!(
+            "note: stats are only recorded when built with `--features metrics` or \
+             `--features allocations`"
+        );
+    }
+}
+
+/// Runs all selected days, reporting failures without stopping the remaining days
+pub fn run_selection(
+    selection: Selection,
+    part: Option<u8>,
+    input: &Input,
+    stats: bool,
+    cache: bool,
+) -> ExitCode {
+    check_stats(stats);
+    let cache = open_cache(cache, stats);
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in selection.days() {
+        if selection == Selection::All {
+            println!("Day {}", day.number);
+        }
+
+        if let Err(error) = run(&day, part, input, stats, cache.as_ref()) {
+            eprintln!("error: {}", error);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+/// Outcome of solving a single part
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Solved(Answer),
+    /// Solved before for the same input and code
+    Cached(Answer),
+    /// Part 2 isn't solved yet
+    Unsolved,
+    /// The input couldn't be read or parsed
+    Failed(String),
+    Panicked(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Solved(answer) => write!(f, "{}", answer),
+            Outcome::Cached(answer) => write!(f, "{} (cached)", answer),
+            Outcome::Unsolved => write!(f, "-"),
+            Outcome::Failed(message) => {
+                write!(f, "failed: {}", message.lines().next().unwrap_or(""))
+            }
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// Solves the parts of all days on a pool of worker threads, then prints a table of the answers
+/// and durations sorted by day. Each part reads and parses its own input, which is included in
+/// its duration. The metrics of each part, parsing included, are printed below the table if
+/// requested. Parts that are cached are not solved again.
+pub fn run_all(
+    part: Option<u8>,
+    input: &Input,
+    workers: usize,
+    stats: bool,
+    cache: bool,
+) -> ExitCode {
+    check_stats(stats);
+    let cache = open_cache(cache, stats);
+    let jobs: Vec<(Day, u8)> = days::registry()
+        .into_iter()
+        .flat_map(|day| [(day, 1), (day, 2)])
+        .filter(|(_, p)| part.is_none_or(|part| part == *p))
+        .collect();
+
+    // Panics are reported in the table, so keep the default hook from printing them as well
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let completed = pool::run(jobs.clone(), workers, |(day, part)| {
+        let string = input.source.read(&day)?;
+        if part > day.parts {
+            return Ok((None, vec![], false));
+        }
+        let key = cache
+            .as_ref()
+            .map(|cache| cache.key(day.number, part, &string, &input.params(&day)));
+        if let Some(answer) = key.and_then(|key| cache.as_ref()?.get(key)) {
+            return Ok((Some(answer), vec![], true));
+        }
+
+        let (answer, metrics) = recorded(|| {
+            let solver = parse_input(&day, input, string, day.parse)?;
+            Ok::<_, Error>(match part {
+                1 => Some(solver.part_1()),
+                _ => solver.part_2(),
+            })
+        });
+        let Some(answer) = answer? else {
+            return Err(Error::NoAnswer {
+                path: input.source.name(day.number),
+                part,
+            });
+        };
+        if let (Some(cache), Some(key)) = (&cache, key) {
+            store(cache, key, &answer);
+        }
+
+        Ok::<_, Error>((Some(answer), metrics, false))
+    });
+    let elapsed = start.elapsed();
+    panic::set_hook(hook);
+
+    let mut recorded: Vec<(u8, u8, Metrics)> = vec![];
+    let rows: Vec<(u8, u8, Outcome, Duration)> = jobs
+        .iter()
+        .zip(completed)
+        .map(|(&(day, part), completed)| {
+            if let Ok(Ok((_, metrics, _))) = &completed.result {
+                recorded.push((day.number, part, metrics.clone()));
+            }
+            let outcome = match completed.result {
+                Ok(Ok((Some(answer), _, true))) => Outcome::Cached(answer),
+                Ok(Ok((Some(answer), _, false))) => Outcome::Solved(answer),
+                Ok(Ok((None, _, _))) => Outcome::Unsolved,
+                Ok(Err(error)) => Outcome::Failed(error.to_string()),
+                Err(message) => Outcome::Panicked(message),
+            };
+
+            (day.number, part, outcome, completed.duration)
+        })
+        .collect();
+
+    let width = rows
+        .iter()
+        .map(|(_, _, outcome, _)| outcome.to_string().len())
+        .chain(["answer".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}",
+        "day", "part", "answer", "time"
+    );
+    for (day, part, outcome, duration) in &rows {
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>12}",
+            day,
+            part,
+            outcome.to_string(),
+            format!("{:.2?}", duration)
+        );
+    }
+
+    let count = |matches: fn(&Outcome) -> bool| rows.iter().filter(|row| matches(&row.2)).count();
+    let failed = count(|outcome| matches!(outcome, Outcome::Failed(_) | Outcome::Panicked(_)));
+    println!(
+        "{} parts solved, {} unsolved, {} failed in {:.2?} ({:.2?} of work on {} workers)",
+        count(|outcome| matches!(outcome, Outcome::Solved(_) | Outcome::Cached(_))),
+        count(|outcome| *outcome == Outcome::Unsolved),
+        failed,
+        elapsed,
+        rows.iter().map(|row| row.3).sum::<Duration>(),
+        workers
+    );
+
+    if stats {
+        for (day, part, metrics) in recorded.iter().filter(|row| !row.2.is_empty()) {
+            print!("Day {} part {}\n{}", day, part, metrics::report(metrics));
+        }
+    }
+
+    // Show the full errors, such as the excerpt of a parse error, once per day
+    let mut reported = vec![];
+    for (day, _, outcome, _) in &rows {
+        if let Outcome::Failed(message) = outcome {
+            if !reported.contains(day) {
+                eprintln!("error: {}", message);
+                reported.push(*day);
+            }
+        }
+    }
+
+    match failed {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Runs the selected days against their inputs and compares the answers with the accepted ones,
+/// failing when an answer changed. Days without an input are skipped.
+pub fn verify(selection: Selection) -> ExitCode {
+    let answers = match Answers::load() {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in selection.days() {
+        let solver = match load(&day, &Input::default()) {
+            Ok(solver) => solver,
+            Err(Error::Input { path, error }) if error.kind() == io::ErrorKind::NotFound => {
+                println!("Day {}: skipped, {} not found", day.number, path);
+                continue;
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        for (part, answer) in [(1, Some(solver.part_1())), (2, solver.part_2())] {
+            if part > day.parts {
+                continue;
+            }
+            print!("Day {} part {}: ", day.number, part);
+            let Some(answer) = answer else {
+                println!("no answer found");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            };
+
+            match answers.accepted(day.number, part) {
+                Some(accepted) if answer == accepted => println!("ok ({})", answer),
+                Some(accepted) => {
+                    print!("changed, expected {} but got {}", accepted, answer);
+                    match answers.verdict_of(day.number, part, &answer.to_string()) {
+                        Some(Verdict::TooHigh) => println!(" (rejected before as too high)"),
+                        Some(Verdict::TooLow) => println!(" (rejected before as too low)"),
+                        Some(Verdict::Wrong) => println!(" (rejected before)"),
+                        _ => println!(),
+                    }
+                    exit_code = ExitCode::FAILURE;
+                }
+                None => println!("no accepted answer yet ({})", answer),
+            }
+        }
+    }
+
+    exit_code
+}
+
+/// Entry point of the per-day binaries
+pub fn run_day(number: u8) -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match cli::parse_day(number, &args) {
+        Ok((mut input, animate)) => {
+            if let Err(error) = input.load_config() {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            }
+
+            match animate {
+                None => run_selection(Selection::Day(number), None, &input, false, false),
+                Some(options) => animate::run(number, &input, &options),
+            }
+        }
+        Err(message) => {
+            eprintln!(
+                "{}\n{}",
+                message,
+                cli::DAY_USAGE.replace("dayN", &format!("day{}", number))
+            );
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Generates the skeleton of a new day: the solution from `templates/day.rs`, its binary, an
+//! empty sample with a sidecar for its answers, its fuzz target, its registration and an entry in
+//! the answer history.
+
+use crate::answers::{self, Answers};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+pub const TEMPLATE: &str = include_str!("../templates/day.rs");
+pub const REGISTRY: &str = "src/days/mod.rs";
+pub const FUZZ_MANIFEST: &str = "fuzz/Cargo.toml";
+
+const EXPECTED: &str = "\
+# Answers of the sample, checked once uncommented
+# part1 <answer>
+# part2 <answer>
+";
+
+/// Files generated for the day along with their contents, relative to the crate root
+pub fn files(day: u8) -> Vec<(PathBuf, String)> {
+    vec![
+        (
+            PathBuf::from(format!("src/days/day{}.rs", day)),
+            TEMPLATE.replace("{day}", &day.to_string()),
+        ),
+        (
+            PathBuf::from(format!("src/bin/day{}.rs", day)),
+            format!(
+                "use std::process::ExitCode;\n\n\
+                 fn main() -> ExitCode {{\n    aoc24::runner::run_day({})\n}}\n",
+                day
+            ),
+        ),
+        (
+            PathBuf::from(format!("samples/day{}.txt", day)),
+            String::new(),
+        ),
+        (
+            PathBuf::from(format!("samples/day{}/sample.expected", day)),
+            EXPECTED.to_string(),
+        ),
+        (
+            PathBuf::from(format!("fuzz/fuzz_targets/day{}.rs", day)),
+            format!(
+                "#![no_main]\n\n\
+                 use libfuzzer_sys::fuzz_target;\n\n\
+                 fuzz_target!(|data: &[u8]| aoc24::fuzz::run({}, data));\n",
+                day
+            ),
+        ),
+        // Seeded with the sample, which stays empty until it is filled in
+        (
+            PathBuf::from(format!("fuzz/corpus/day{}/sample", day)),
+            String::new(),
+        ),
+    ]
+}
+
+/// Adds the day's fuzz target to the fuzz crate's manifest
+pub fn add_fuzz_target(manifest: &str, day: u8) -> Result<String, String> {
+    let name = format!("name = \"day{}\"", day);
+    if manifest.lines().any(|line| line == name) {
+        return Err(format!(
+            "Day {} already has a fuzz target in {}",
+            day, FUZZ_MANIFEST
+        ));
+    }
+
+    Ok(format!(
+        "{}\n\n[[bin]]\n{}\npath = \"fuzz_targets/day{}.rs\"\n\
+         test = false\ndoc = false\nbench = false\n",
+        manifest.trim_end(),
+        name,
+        day
+    ))
+}
+
+/// Adds the day's module and registry entry to the days module, keeping both in order
+pub fn register(module: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{}", day);
+    let declaration = format!("pub mod {};", name);
+    let mut lines: Vec<String> = module.lines().map(str::to_string).collect();
+
+    if lines.contains(&declaration) {
+        return Err(format!("Day {} is already registered in {}", day, REGISTRY));
+    }
+    let missing = || format!("Failed to find the registry in {}", REGISTRY);
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub fn registry()"))
+        .ok_or_else(missing)?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "    ]")
+            .ok_or_else(missing)?;
+
+    // Entries of later days may span several lines, so insert before the first one's start
+    let entry = start
+        + lines[start..end]
+            .iter()
+            .position(|line| {
+                line.trim_start()
+                    .strip_prefix("Day::of::<day")
+                    .and_then(|rest| rest.split_once("::"))
+                    .and_then(|(number, _)| number.parse::<u8>().ok())
+                    .is_some_and(|number| number > day)
+            })
+            .unwrap_or(end - start);
+    lines.insert(entry, format!("        Day::of::<{}::Problem>(),", name));
+
+    // Modules are sorted like rustfmt sorts them, as strings
+    let modules = lines
+        .iter()
+        .take_while(|line| line.starts_with("pub mod "))
+        .count();
+    let position = lines[..modules]
+        .iter()
+        .position(|line| line.trim_start_matches("pub mod ").trim_end_matches(';') > name.as_str())
+        .unwrap_or(modules);
+    lines.insert(position, declaration);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Generates the day in the crate at the given root, refusing to touch any existing day. Returns
+/// the paths of the files that were written.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day '{}', expected 1 to 25", day));
+    }
+
+    let files = files(day);
+    if let Some((path, _)) = files.iter().find(|(path, _)| root.join(path).exists()) {
+        return Err(format!("Day {} already exists: {}", day, path.display()));
+    }
+
+    let registry = root.join(REGISTRY);
+    let module = fs::read_to_string(&registry)
+        .map_err(|error| format!("Failed to read {}: {}", registry.display(), error))?;
+    let module = register(&module, day)?;
+
+    let fuzz_manifest = root.join(FUZZ_MANIFEST);
+    let manifest = fs::read_to_string(&fuzz_manifest)
+        .map_err(|error| format!("Failed to read {}: {}", fuzz_manifest.display(), error))?;
+    let manifest = add_fuzz_target(&manifest, day)?;
+
+    let history = root.join(answers::PATH);
+    let mut answers = match fs::read_to_string(&history) {
+        Ok(string) => Answers::from_string(&string)
+            .map_err(|error| format!("Failed to parse {}: {}", history.display(), error))?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Answers::default(),
+        Err(error) => return Err(format!("Failed to read {}: {}", history.display(), error)),
+    };
+    answers.started.push(day);
+
+    let mut written = vec![];
+    for (path, contents) in files {
+        write(&root.join(&path), &contents)?;
+        written.push(path);
+    }
+    write(&registry, &module)?;
+    written.push(PathBuf::from(REGISTRY));
+    write(&fuzz_manifest, &manifest)?;
+    written.push(PathBuf::from(FUZZ_MANIFEST));
+    write(&history, &answers.to_string())?;
+    written.push(PathBuf::from(answers::PATH));
+
+    Ok(written)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create {}: {}", parent.display(), error))?;
+    }
+
+    fs::write(path, contents)
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+}
+
+/// Generates the day in the current directory, which should be the crate root
+pub fn run(day: u8) -> ExitCode {
+    match create(Path::new("."), day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const MODULE: &str = "\
+pub mod day1;
+pub mod day2;
+pub mod day9;
+
+use crate::solution::Day;
+
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::of::<day1::Problem>(),
+        Day::of::<day2::Problem>(),
+        Day::of::<day9::Problem>()
+            .animated::<day9::Problem>(),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        let module = register(MODULE, 12).unwrap();
+
+        assert!(module.starts_with("pub mod day1;\npub mod day12;\npub mod day2;\n"));
+        assert!(module.ends_with(
+            "        Day::of::<day9::Problem>()\n            .animated::<day9::Problem>(),\n        \
+             Day::of::<day12::Problem>(),\n    ]\n}\n"
+        ));
+
+        let module = register(MODULE, 3).unwrap();
+        assert!(module.contains(
+            "Day::of::<day2::Problem>(),\n        Day::of::<day3::Problem>(),\n        \
+             Day::of::<day9::Problem>()\n"
+        ));
+
+        assert_eq!(
+            Err("Day 2 is already registered in src/days/mod.rs".to_string()),
+            register(MODULE, 2)
+        );
+    }
+
+    #[test]
+    fn test_add_fuzz_target() {
+        let manifest = "[package]\nname = \"aoc24-fuzz\"\n";
+
+        assert_eq!(
+            Ok(
+                "[package]\nname = \"aoc24-fuzz\"\n\n[[bin]]\nname = \"day17\"\n\
+                path = \"fuzz_targets/day17.rs\"\ntest = false\ndoc = false\nbench = false\n"
+                    .to_string()
+            ),
+            add_fuzz_target(manifest, 17)
+        );
+        assert!(add_fuzz_target(&add_fuzz_target(manifest, 17).unwrap(), 17).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let root = env::temp_dir().join(format!("aoc24-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root.join(REGISTRY), MODULE).unwrap();
+        write(&root.join(answers::PATH), "2 1 accepted 524\n").unwrap();
+        write(
+            &root.join(FUZZ_MANIFEST),
+            "[package]\nname = \"aoc24-fuzz\"\n",
+        )
+        .unwrap();
+
+        let written = create(&root, 17).unwrap();
+
+        assert_eq!(9, written.len());
+        let solution = fs::read_to_string(root.join("src/days/day17.rs")).unwrap();
+        assert!(solution.contains("const DAY: u8 = 17;"));
+        assert!(solution.contains("include_str!(\"../../samples/day17.txt\")"));
+        assert!(root.join("samples/day17/sample.expected").exists());
+        assert!(root.join("fuzz/corpus/day17/sample").exists());
+        assert!(fs::read_to_string(root.join(REGISTRY))
+            .unwrap()
+            .contains("Day::of::<day17::Problem>(),"));
+        assert_eq!(
+            vec![17],
+            Answers::from_string(&fs::read_to_string(root.join(answers::PATH)).unwrap())
+                .unwrap()
+                .started
+        );
+
+        // Nothing is overwritten, not even a day that is only partly there
+        assert_eq!(
+            Err("Day 17 already exists: src/days/day17.rs".to_string()),
+            create(&root, 17)
+        );
+        write(&root.join("samples/day18.txt"), "sample").unwrap();
+        assert_eq!(
+            Err("Day 18 already exists: samples/day18.txt".to_string()),
+            create(&root, 18)
+        );
+        assert!(!root.join("src/days/day18.rs").exists());
+        assert!(create(&root, 26).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,294 @@
+use crate::metrics;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Outcome of a search: the cheapest cost of every explored state along with the predecessors
+/// through which that cost is reached. States with several equally cheap predecessors keep them
+/// all, so the predecessors form a graph of all cheapest paths.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    pub start: S,
+    pub goal: Option<S>,
+    pub costs: HashMap<S, C>,
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Copy + Eq + Hash, C: Copy> Search<S, C> {
+    /// Cost of the cheapest path to the goal, if one was reached
+    pub fn cost(&self) -> Option<C> {
+        self.costs.get(self.goal.as_ref()?).copied()
+    }
+
+    /// One of the cheapest paths from the start to the goal, including both
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal?)
+    }
+
+    /// One of the cheapest paths from the start to the given state, including both
+    pub fn path_to(&self, state: S) -> Option<Vec<S>> {
+        self.costs.get(&state)?;
+
+        let mut path = vec![state];
+        while *path.last()? != self.start {
+            path.push(*self.predecessors.get(path.last()?)?.first()?);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Number of distinct cheapest paths from the start to the given state
+    pub fn path_count(&self, state: S) -> usize {
+        let mut counts = HashMap::new();
+        self.path_count_cached(state, &mut counts)
+    }
+
+    fn add_predecessor(&mut self, state: S, previous: S) {
+        let predecessors = self.predecessors.entry(state).or_default();
+        if !predecessors.contains(&previous) {
+            predecessors.push(previous);
+        }
+    }
+
+    fn path_count_cached(&self, state: S, counts: &mut HashMap<S, usize>) -> usize {
+        if !self.costs.contains_key(&state) {
+            return 0;
+        }
+        if state == self.start {
+            return 1;
+        }
+        if let Some(&count) = counts.get(&state) {
+            return count;
+        }
+
+        let count = self.predecessors[&state]
+            .iter()
+            .map(|&previous| self.path_count_cached(previous, counts))
+            .sum();
+        counts.insert(state, count);
+        count
+    }
+}
+
+/// Frontier entry, ordered so that the binary heap pops the lowest priority first
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+/// A* search from the start until a state satisfying the goal is reached, or until all
+/// reachable states have been explored. The heuristic estimates the remaining cost to the goal
+/// and must never overestimate it.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Search<S, C>
+where
+    S: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        start,
+        goal: None,
+        costs: HashMap::from([(start, C::default())]),
+        predecessors: HashMap::new(),
+    };
+    let mut frontier = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+
+    while let Some(Queued {
+        priority,
+        cost,
+        state,
+    }) = frontier.pop()
+    {
+        // Keep going until all equally cheap paths to the goal are known
+        if let Some(goal_cost) = search.cost() {
+            if priority > goal_cost {
+                break;
+            }
+        }
+
+        // Skip outdated entries of states that were reached more cheaply since
+        if search.costs[&state] < cost {
+            metrics::count("astar.outdated");
+            continue;
+        }
+        metrics::count("astar.popped");
+
+        if search.goal.is_none() && goal(&state) {
+            search.goal = Some(state);
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+
+            match search.costs.entry(next) {
+                Entry::Occupied(entry) if next_cost > *entry.get() => continue,
+                Entry::Occupied(entry) if next_cost == *entry.get() => {
+                    search.add_predecessor(next, state);
+                    continue;
+                }
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+
+            search.predecessors.insert(next, vec![state]);
+            frontier.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's search, which is A* without a heuristic
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, goal, |_| C::default())
+}
+
+/// Breadth-first search for unweighted graphs, the cost being the number of steps
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search {
+        start,
+        goal: None,
+        costs: HashMap::from([(start, 0)]),
+        predecessors: HashMap::new(),
+    };
+    let mut frontier = VecDeque::from([start]);
+
+    while let Some(state) = frontier.pop_front() {
+        let cost = search.costs[&state];
+        metrics::count("bfs.popped");
+
+        if search.goal.is_none() && goal(&state) {
+            search.goal = Some(state);
+        }
+        // Only finish the current layer, which may still add equally short paths to the goal
+        if search.cost().is_some_and(|goal_cost| cost >= goal_cost) {
+            continue;
+        }
+
+        for next in successors(&state) {
+            match search.costs.get(&next) {
+                Some(&next_cost) if next_cost == cost + 1 => search.add_predecessor(next, state),
+                Some(_) => {}
+                None => {
+                    search.costs.insert(next, cost + 1);
+                    search.predecessors.insert(next, vec![state]);
+                    frontier.push_back(next);
+                }
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted graph: 0 -> 1 (1), 0 -> 2 (4), 1 -> 2 (2), 1 -> 3 (5), 2 -> 3 (3)
+    fn edges(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 2), (3, 5)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(0, edges, |&node| node == 3);
+
+        assert_eq!(Some(6), search.cost());
+        assert_eq!(Some(vec![0, 1, 3]), search.path());
+        assert_eq!(2, search.path_count(3)); // Through 1 -> 3 and 1 -> 2 -> 3
+        assert!(dijkstra(3, edges, |&node| node == 0).cost().is_none());
+    }
+
+    #[test]
+    fn test_astar() {
+        // Grid walk towards (5, 5) with the manhattan distance as heuristic
+        let goal = (5i32, 5i32);
+        let search = astar(
+            (0i32, 0i32),
+            |&(x, y)| [(x + 1, y), (x, y + 1), (x - 1, y)].map(|next| (next, 1u32)),
+            |&position| position == goal,
+            |&(x, y)| goal.0.abs_diff(x) + goal.1.abs_diff(y),
+        );
+
+        assert_eq!(Some(10), search.cost());
+        assert_eq!(11, search.path().unwrap().len());
+        assert_eq!(252, search.path_count(goal)); // 10 choose 5
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs(
+            1u32,
+            |&n| [n * 2, n + 1].into_iter().filter(|&next| next <= 20),
+            |&n| n == 10,
+        );
+
+        assert_eq!(Some(4), search.cost());
+        assert_eq!(Some(vec![1, 2, 4, 5, 10]), search.path());
+        assert_eq!(1, search.path_count(10)); // Reaching 2 twice from 1 is still a single path
+    }
+}
@@ -0,0 +1,310 @@
+use crate::animate::{Animate, Frames};
+use crate::answer::Answer;
+use crate::image::{Picture, Render};
+use crate::parse::ParseError;
+use std::any::TypeId;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Value of a puzzle parameter, written as an integer or as double quoted text
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Param {
+    Integer(i64),
+    Text(String),
+}
+
+impl FromStr for Param {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+        {
+            Some(text) => Ok(Param::Text(text.to_string())),
+            None => string.parse().map(Param::Integer).map_err(|_| {
+                format!(
+                    "Invalid value '{}', expected an integer or \"text\"",
+                    string
+                )
+            }),
+        }
+    }
+}
+
+/// Type of the values a parameter takes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParamKind {
+    Integer,
+    Text,
+}
+
+impl ParamKind {
+    pub fn of(value: &Param) -> Self {
+        match value {
+            Param::Integer(_) => ParamKind::Integer,
+            Param::Text(_) => ParamKind::Text,
+        }
+    }
+
+    /// Description of the values for error messages
+    pub fn expected(self) -> &'static str {
+        match self {
+            ParamKind::Integer => "an integer",
+            ParamKind::Text => "double quoted text",
+        }
+    }
+}
+
+/// Puzzle parameters that are not part of the input, like the dimensions of day 14's area.
+/// These differ between the sample and the real input, so they can be overridden. Later values
+/// take precedence over earlier ones of the same key.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Params {
+    values: Vec<(String, Param)>,
+}
+
+impl Params {
+    fn value(&self, key: &str) -> Option<&Param> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Integer value of the key, or `None` if it is missing or text
+    pub fn get(&self, key: &str) -> Option<i64> {
+        match self.value(key)? {
+            Param::Integer(value) => Some(*value),
+            Param::Text(_) => None,
+        }
+    }
+
+    /// Integer value of the key converted to the type of the range, failing when it lies outside
+    /// of the range
+    pub fn get_in<T>(&self, key: &str, range: RangeInclusive<T>) -> Result<Option<T>, ParseError>
+    where
+        T: TryFrom<i64> + PartialOrd + Display,
+    {
+        let Some(value) = self.get(key) else {
+            return Ok(None);
+        };
+
+        match T::try_from(value) {
+            Ok(value) if range.contains(&value) => Ok(Some(value)),
+            _ => Err(ParseError::parameter(format!(
+                "expected '{}' from {} to {}, found {}",
+                key,
+                range.start(),
+                range.end(),
+                value
+            ))),
+        }
+    }
+
+    /// Text value of the key, or `None` if it is missing or an integer
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.value(key)? {
+            Param::Text(text) => Some(text),
+            Param::Integer(_) => None,
+        }
+    }
+
+    /// All keys along with their values, including those overridden by later values
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Param)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn set(&mut self, key: &str, value: i64) {
+        self.insert(key, Param::Integer(value));
+    }
+
+    pub fn insert(&mut self, key: &str, value: Param) {
+        self.values.push((key.to_string(), value));
+    }
+
+    /// Adds the other parameters, which take precedence over these
+    pub fn extend(&mut self, other: &Params) {
+        self.values.extend(other.values.iter().cloned());
+    }
+}
+
+impl From<&[(&str, i64)]> for Params {
+    fn from(pairs: &[(&str, i64)]) -> Self {
+        let mut params = Params::default();
+        for (key, value) in pairs {
+            params.set(key, *value);
+        }
+        params
+    }
+}
+
+impl FromStr for Params {
+    type Err = String;
+
+    /// Parses comma separated `key=value` pairs, where text values can't contain commas
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut params = Params::default();
+
+        for pair in string.split(',') {
+            match pair.split_once('=') {
+                Some((key, value)) if !key.is_empty() => match value.parse() {
+                    Ok(value) => params.insert(key, value),
+                    Err(_) => return Err(format!("Invalid value '{}' of '{}'", value, key)),
+                },
+                _ => return Err(format!("Invalid parameter '{}', expected key=value", pair)),
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+/// A day's puzzle: parses its input once, after which both parts can be solved
+pub trait Solution: Sized {
+    const DAY: u8;
+    /// Example input from the puzzle description
+    const SAMPLE: &'static str;
+    /// Parameters the sample is solved with, where they differ from the real input's
+    const SAMPLE_PARAMS: &'static [(&'static str, i64)] = &[];
+    /// Parameters [`Solution::parse_with`] looks at, along with the type of their values
+    const PARAMS: &'static [(&'static str, ParamKind)] = &[];
+
+    type Part1: Into<Answer> + 'static;
+    type Part2: Into<Answer> + 'static;
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    /// Parses the input using the given parameters, which most days don't have
+    fn parse_with(input: &str, _params: &Params) -> Result<Self, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part_1(&self) -> Self::Part1;
+
+    /// Returns `None` when the input has no answer. Days that haven't solved part 2 yet have
+    /// [`Infallible`] answers, so that they always return `None`.
+    fn part_2(&self) -> Option<Self::Part2>;
+}
+
+/// Object safe counterpart of [`Solution`] with the answers converted to [`Answer`], so that
+/// days with different answer types can be stored side by side in the registry
+pub trait Solver {
+    fn part_1(&self) -> Answer;
+
+    fn part_2(&self) -> Option<Answer>;
+}
+
+impl<S: Solution> Solver for S {
+    fn part_1(&self) -> Answer {
+        Solution::part_1(self).into()
+    }
+
+    fn part_2(&self) -> Option<Answer> {
+        Solution::part_2(self).map(Into::into)
+    }
+}
+
+pub type Parser = fn(&str, &Params) -> Result<Box<dyn Solver>, ParseError>;
+pub type Animator = fn(&str, &Params) -> Result<Frames, ParseError>;
+pub type Renderer = fn(&str, &Params) -> Result<Picture, ParseError>;
+
+/// Registry entry for a single day
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub number: u8,
+    pub sample: &'static str,
+    pub sample_params: &'static [(&'static str, i64)],
+    pub params: &'static [(&'static str, ParamKind)],
+    /// Number of parts solved, which is 1 as long as part 2 is [`Infallible`]
+    pub parts: u8,
+    pub parse: Parser,
+    /// Only set for days that implement [`Animate`]
+    pub animate: Option<Animator>,
+    /// Only set for days that implement [`Render`]
+    pub render: Option<Renderer>,
+}
+
+impl Day {
+    pub fn of<S: Solution + 'static>() -> Self {
+        Self {
+            number: S::DAY,
+            sample: S::SAMPLE,
+            sample_params: S::SAMPLE_PARAMS,
+            params: S::PARAMS,
+            parts: match TypeId::of::<S::Part2>() == TypeId::of::<Infallible>() {
+                true => 1,
+                false => 2,
+            },
+            parse: |input, params| match S::parse_with(input, params) {
+                Ok(solution) => Ok(Box::new(solution)),
+                Err(error) => Err(error.in_day(S::DAY)),
+            },
+            animate: None,
+            render: None,
+        }
+    }
+
+    /// Adds the animation of the given day, which is expected to be this day
+    pub fn animated<S: Animate + 'static>(self) -> Self {
+        Self {
+            animate: Some(|input, params| match S::parse_with(input, params) {
+                Ok(solution) => Ok(solution.frames()),
+                Err(error) => Err(error.in_day(S::DAY)),
+            }),
+            ..self
+        }
+    }
+
+    /// Adds the image of the given day, which is expected to be this day
+    pub fn rendered<S: Render + 'static>(self) -> Self {
+        Self {
+            render: Some(|input, params| match S::parse_with(input, params) {
+                Ok(solution) => Ok(solution.render()),
+                Err(error) => Err(error.in_day(S::DAY)),
+            }),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let params: Params = "width=11,height=7,width=12".parse().unwrap();
+
+        assert_eq!(Some(12), params.get("width"));
+        assert_eq!(Some(7), params.get("height"));
+        assert_eq!(None, params.get("depth"));
+        assert!("width".parse::<Params>().is_err());
+        assert!("width=wide".parse::<Params>().is_err());
+
+        let mut params: Params = "word=\"SAMX\",limit=3".parse().unwrap();
+        assert_eq!(Some("SAMX"), params.text("word"));
+        assert_eq!(None, params.get("word"));
+        assert_eq!(None, params.text("limit"));
+        params.extend(&"limit=5".parse().unwrap());
+        assert_eq!(Some(5), params.get("limit"));
+
+        assert_eq!(Ok(Some(5u32)), params.get_in("limit", 0..=10));
+        assert_eq!(Ok(None), params.get_in::<u32>("depth", 0..=10));
+        assert_eq!(
+            "expected 'limit' from 0 to 4, found 5",
+            params.get_in("limit", 0..=4u8).unwrap_err().to_string()
+        );
+        params.set("limit", -1);
+        assert!(params.get_in("limit", 0..=u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(2, crate::days::get(1).unwrap().parts);
+        assert_eq!(1, crate::days::get(16).unwrap().parts);
+    }
+}
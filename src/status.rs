@@ -0,0 +1,232 @@
+//! Overview of every day of the event: which parts are solved and verified, how many wrong answers
+//! were submitted, and how long the day took when it was last benchmarked.
+
+use crate::answers::{Answers, Verdict};
+use crate::bench;
+use crate::days;
+use crate::json::Value;
+use crate::solution::Day;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use std::time::Duration;
+
+pub const DAYS: u8 = 25;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Progress {
+    Missing,
+    /// Solved without an accepted answer in the history
+    Unverified,
+    Verified,
+}
+
+impl Progress {
+    /// ANSI colour of the progress in the terminal table
+    fn colour(&self) -> &'static str {
+        match self {
+            Progress::Missing => "\x1b[2m",
+            Progress::Unverified => "\x1b[33m",
+            Progress::Verified => "\x1b[32m",
+        }
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Progress::Missing => "-",
+            Progress::Unverified => "unverified",
+            Progress::Verified => "verified",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Status {
+    pub day: u8,
+    pub parts: [Progress; 2],
+    /// Submitted answers that were rejected, of both parts
+    pub wrong: usize,
+    /// Total of the median durations when last benchmarked
+    pub runtime: Option<Duration>,
+}
+
+impl Status {
+    fn runtime(&self) -> String {
+        match self.runtime {
+            Some(runtime) => format!("{:.2?}", runtime),
+            None => "-".to_string(),
+        }
+    }
+}
+
+/// Status of all days of the event, solved or not
+pub fn collect(registry: &[Day], answers: &Answers, bench: &Value) -> Vec<Status> {
+    (1..=DAYS)
+        .map(|number| {
+            let solved = registry
+                .iter()
+                .find(|day| day.number == number)
+                .map_or(0, |day| day.parts);
+            let progress = |part: u8| match answers.accepted(number, part) {
+                _ if part > solved => Progress::Missing,
+                Some(_) => Progress::Verified,
+                None => Progress::Unverified,
+            };
+
+            Status {
+                day: number,
+                parts: [progress(1), progress(2)],
+                wrong: answers
+                    .attempts
+                    .iter()
+                    .filter(|attempt| attempt.day == number && attempt.verdict != Verdict::Accepted)
+                    .count(),
+                runtime: bench::baseline_total(bench, number),
+            }
+        })
+        .collect()
+}
+
+fn stars(statuses: &[Status]) -> usize {
+    statuses
+        .iter()
+        .flat_map(|status| status.parts)
+        .filter(|&progress| progress == Progress::Verified)
+        .count()
+}
+
+/// Formats the statuses as an aligned table, with the progress of each part coloured if requested
+pub fn table(statuses: &[Status], colour: bool) -> String {
+    let mut table = format!(
+        "{:>3}  {:<10}  {:<10}  {:>5}  {:>10}\n",
+        "day", "part 1", "part 2", "wrong", "runtime"
+    );
+
+    for status in statuses {
+        let [part_1, part_2] = status.parts.map(|progress| match colour {
+            true => format!("{}{:<10}\x1b[0m", progress.colour(), progress),
+            false => format!("{:<10}", progress),
+        });
+        table += &format!(
+            "{:>3}  {}  {}  {:>5}  {:>10}\n",
+            status.day,
+            part_1,
+            part_2,
+            status.wrong,
+            status.runtime()
+        );
+    }
+
+    table + &format!("\n{} of {} stars\n", stars(statuses), 2 * DAYS)
+}
+
+/// Formats the statuses as a Markdown table, to be pasted into the README
+pub fn markdown(statuses: &[Status]) -> String {
+    let mut markdown = "| Day | Part 1 | Part 2 | Wrong answers | Runtime |\n\
+                        |----:|:------:|:------:|--------------:|--------:|\n"
+        .to_string();
+
+    for status in statuses {
+        markdown += &format!(
+            "| {} | {} | {} | {} | {} |\n",
+            status.day,
+            status.parts[0],
+            status.parts[1],
+            status.wrong,
+            status.runtime()
+        );
+    }
+
+    markdown + &format!("\n{} of {} stars\n", stars(statuses), 2 * DAYS)
+}
+
+/// Prints the status of all days, as Markdown if requested. The runtimes come from the last
+/// benchmark of each day.
+pub fn run(markdown: bool) -> ExitCode {
+    let answers = match Answers::load() {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let bench = bench::load_baseline(bench::LAST).unwrap_or(Value::Object(vec![]));
+    let statuses = collect(&days::registry(), &answers, &bench);
+
+    match markdown {
+        true => print!("{}", self::markdown(&statuses)),
+        false => {
+            let colour = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+            print!("{}", table(&statuses, colour));
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses() -> Vec<Status> {
+        let answers = Answers::from_string(
+            "1 1 accepted 11\n\
+             1 2 too-low 30\n\
+             16 1 too-high 7037\n\
+             16 1 accepted 7036\n",
+        )
+        .unwrap();
+        let bench = Value::parse("{\"1\":{\"parse\":1500,\"part1\":500,\"part2\":1000}}").unwrap();
+
+        collect(&days::registry(), &answers, &bench)
+    }
+
+    #[test]
+    fn test_collect() {
+        let statuses = statuses();
+
+        assert_eq!(25, statuses.len());
+        assert_eq!(
+            Status {
+                day: 1,
+                parts: [Progress::Verified, Progress::Unverified],
+                wrong: 1,
+                runtime: Some(Duration::from_micros(3)),
+            },
+            statuses[0]
+        );
+        assert_eq!([Progress::Verified, Progress::Missing], statuses[15].parts);
+        assert_eq!(1, statuses[15].wrong);
+        assert_eq!([Progress::Missing; 2], statuses[24].parts);
+        assert_eq!(None, statuses[24].runtime);
+    }
+
+    #[test]
+    fn test_table() {
+        let table = table(&statuses(), false);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!("day  part 1      part 2      wrong     runtime", lines[0]);
+        assert_eq!("  1  verified    unverified      1      3.00µs", lines[1]);
+        assert_eq!(" 16  verified    -               1           -", lines[16]);
+        assert_eq!("2 of 50 stars", lines[27]);
+
+        assert!(super::table(&statuses(), true).contains("\x1b[32mverified  \x1b[0m"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = markdown(&statuses());
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(
+            "| Day | Part 1 | Part 2 | Wrong answers | Runtime |",
+            lines[0]
+        );
+        assert_eq!("| 1 | verified | unverified | 1 | 3.00µs |", lines[2]);
+        assert_eq!("| 25 | - | - | 0 | - |", lines[26]);
+    }
+}
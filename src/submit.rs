@@ -0,0 +1,320 @@
+use crate::answers::{Answers, Attempt, Verdict};
+use crate::days;
+use crate::http::{self, Client, Request};
+use crate::inputs::{self, BASE_URL, USER_AGENT};
+use crate::runner::{self, Input};
+use std::fmt::{Display, Formatter};
+use std::process::ExitCode;
+
+/// Reason to not submit an answer, derived from the earlier attempts
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Rejection {
+    AlreadyAccepted(String),
+    KnownWrong(Verdict),
+    /// The answer is at least an answer that was too high
+    AboveBound(String),
+    /// The answer is at most an answer that was too low
+    BelowBound(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyAccepted(answer) => {
+                write!(f, "this part was already solved with {}", answer)
+            }
+            Rejection::KnownWrong(Verdict::TooHigh) => write!(f, "it was too high before"),
+            Rejection::KnownWrong(Verdict::TooLow) => write!(f, "it was too low before"),
+            Rejection::KnownWrong(_) => write!(f, "it was wrong before"),
+            Rejection::AboveBound(bound) => write!(f, "{} was already too high", bound),
+            Rejection::BelowBound(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+/// Checks whether an answer could still be right given the earlier attempts
+pub fn check(answers: &Answers, day: u8, part: u8, answer: &str) -> Result<(), Rejection> {
+    if let Some(accepted) = answers.accepted(day, part) {
+        return Err(Rejection::AlreadyAccepted(accepted.to_string()));
+    }
+
+    if let Some(verdict) = answers.verdict_of(day, part, answer) {
+        return Err(Rejection::KnownWrong(verdict));
+    }
+
+    let Ok(number) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+    let bound = |verdict| {
+        answers
+            .for_part(day, part)
+            .filter(move |attempt| attempt.verdict == verdict)
+            .filter_map(|attempt| Some((attempt.answer.parse::<i128>().ok()?, &attempt.answer)))
+    };
+
+    if let Some((_, too_high)) = bound(Verdict::TooHigh).filter(|(n, _)| number >= *n).min() {
+        return Err(Rejection::AboveBound(too_high.clone()));
+    }
+
+    if let Some((_, too_low)) = bound(Verdict::TooLow).filter(|(n, _)| number <= *n).max() {
+        return Err(Rejection::BelowBound(too_low.clone()));
+    }
+
+    Ok(())
+}
+
+/// What the puzzle website made of a submission
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Verdict(Verdict),
+    /// Submitted too soon after a wrong answer, along with the remaining wait if mentioned
+    RateLimited(Option<String>),
+    /// The part is already solved, or part 1 isn't yet
+    WrongLevel,
+    Unknown,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Verdict(Verdict::Accepted) => write!(f, "That's the right answer!"),
+            Outcome::Verdict(Verdict::TooHigh) => write!(f, "Wrong, too high"),
+            Outcome::Verdict(Verdict::TooLow) => write!(f, "Wrong, too low"),
+            Outcome::Verdict(Verdict::Wrong) => write!(f, "Wrong"),
+            Outcome::RateLimited(Some(wait)) => write!(f, "Rate limited, {}", wait),
+            Outcome::RateLimited(None) => write!(f, "Rate limited"),
+            Outcome::WrongLevel => write!(f, "Not solving the right level"),
+            Outcome::Unknown => write!(f, "Unrecognized response"),
+        }
+    }
+}
+
+/// Interprets the response page of a submission
+pub fn parse_response(page: &str) -> Outcome {
+    if page.contains("That's the right answer") {
+        Outcome::Verdict(Verdict::Accepted)
+    } else if page.contains("That's not the right answer") {
+        Outcome::Verdict(if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if page.contains("You gave an answer too recently") {
+        Outcome::RateLimited(
+            page.split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| format!("{} left to wait", wait)),
+        )
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    }
+}
+
+pub struct Submitter<C: Client> {
+    client: C,
+    base_url: String,
+    session: String,
+}
+
+impl<C: Client> Submitter<C> {
+    pub fn new(client: C, base_url: &str, session: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Outcome, http::Error> {
+        let body = format!("level={}&answer={}", part, encode(answer));
+        let request = Request::post(&format!("{}/day/{}/answer", self.base_url, day), &body)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT);
+        let response = self.client.send(&request)?;
+
+        match response.status {
+            200 => Ok(parse_response(&response.body)),
+            status => Err(http::Error::Response(format!("status {}", status))),
+        }
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, for form bodies
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Submits an answer, or the computed answer when none is given, and records the verdict
+pub fn run(day: u8, part: u8, answer: Option<String>) -> ExitCode {
+    match submit(day, part, answer) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) -> Result<bool, String> {
+    let mut answers = Answers::load()?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = days::get(day).ok_or(format!("Unknown day {}", day))?;
+            let solver =
+                runner::load(&day, &Input::default()).map_err(|error| error.to_string())?;
+
+            match part {
+                1 => solver.part_1(),
+                _ if day.parts < 2 => return Err("Part 2 is not solved yet".to_string()),
+                _ => solver
+                    .part_2()
+                    .ok_or("Found no answer to part 2 for the input")?,
+            }
+            .to_string()
+        }
+    };
+
+    if let Err(rejection) = check(&answers, day, part, &answer) {
+        println!("Not submitting {}: {}", answer, rejection);
+        return Ok(false);
+    }
+
+    let session = inputs::session().map_err(|error| error.to_string())?;
+    let submitter = Submitter::new(http::client_for(BASE_URL), BASE_URL, &session);
+    let outcome = submitter
+        .submit(day, part, &answer)
+        .map_err(|error| format!("Submitting failed: {}", error))?;
+
+    println!("Day {} part {}: {} ({})", day, part, outcome, answer);
+
+    match outcome {
+        Outcome::Verdict(verdict) => {
+            answers.attempts.push(Attempt {
+                day,
+                part,
+                verdict,
+                answer,
+            });
+            answers.save()?;
+
+            Ok(verdict == Verdict::Accepted)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tests::serve;
+    use crate::http::TcpClient;
+
+    fn history() -> Answers {
+        Answers::from_string(
+            "2 2 too-low 549\n\
+             2 2 too-low 554\n\
+             2 2 too-high 600\n\
+             2 2 wrong 570\n\
+             2 1 accepted 524\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = history();
+
+        assert_eq!(Ok(()), check(&answers, 2, 2, "569"));
+        assert_eq!(Ok(()), check(&answers, 2, 2, "not a number"));
+        assert_eq!(
+            Err(Rejection::AlreadyAccepted("524".to_string())),
+            check(&answers, 2, 1, "525")
+        );
+        assert_eq!(
+            Err(Rejection::KnownWrong(Verdict::TooLow)),
+            check(&answers, 2, 2, "549")
+        );
+        assert_eq!(
+            Err(Rejection::KnownWrong(Verdict::Wrong)),
+            check(&answers, 2, 2, "570")
+        );
+        assert_eq!(
+            Err(Rejection::BelowBound("554".to_string())),
+            check(&answers, 2, 2, "550")
+        );
+        assert_eq!(
+            Err(Rejection::AboveBound("600".to_string())),
+            check(&answers, 2, 2, "1000")
+        );
+        assert_eq!(Ok(()), check(&answers, 3, 1, "1"));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            Outcome::Verdict(Verdict::Accepted),
+            parse_response("<main><article><p>That's the right answer! You are one gold star closer to finding the Chief Historian.</p></article></main>")
+        );
+        assert_eq!(
+            Outcome::Verdict(Verdict::TooLow),
+            parse_response(
+                "<p>That's not the right answer; your answer is too low. If you're stuck, ...</p>"
+            )
+        );
+        assert_eq!(
+            Outcome::Verdict(Verdict::TooHigh),
+            parse_response("<p>That's not the right answer; your answer is too high.</p>")
+        );
+        assert_eq!(
+            Outcome::Verdict(Verdict::Wrong),
+            parse_response("<p>That's not the right answer. If you're stuck, ...</p>")
+        );
+        assert_eq!(
+            Outcome::RateLimited(Some("34s left to wait".to_string())),
+            parse_response("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p>")
+        );
+        assert_eq!(
+            Outcome::WrongLevel,
+            parse_response("<p>You don't seem to be solving the right level.  Did you already complete it?</p>")
+        );
+        assert_eq!(Outcome::Unknown, parse_response("<html></html>"));
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, requests) = serve(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+            (500, ""),
+        ]);
+        let submitter = Submitter::new(TcpClient, &url, "secret");
+
+        assert_eq!(
+            Outcome::Verdict(Verdict::TooLow),
+            submitter.submit(2, 2, "549").unwrap()
+        );
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /day/2/answer HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=secret\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=549"));
+
+        assert!(submitter.submit(2, 2, "a b").is_err());
+        assert!(requests.recv().unwrap().ends_with("level=2&answer=a%20b"));
+    }
+}
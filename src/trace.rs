@@ -0,0 +1,260 @@
+//! Leveled events with fields for following what a day does, instead of sprinkling prints around.
+//!
+//! Events are emitted with `event!` and are off unless enabled for their module by a filter like
+//! `day15` or `day6=debug,search=info`, given with `--trace` or the `AOC_TRACE` variable. They are
+//! written to stderr, or to the file given with `--trace-file` or `AOC_TRACE_FILE`.
+//!
+//! ```ignore
+//! trace::event!(Debug, "checked obstruction", position = position, loops = loops);
+//! trace::event!(Trace, "expanding", direction = ?direction);
+//! ```
+//!
+//! Fields are formatted with `Display`, or with `Debug` when the value is prefixed by `?`.
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+pub const FILTER_VAR: &str = "AOC_TRACE";
+pub const FILE_VAR: &str = "AOC_TRACE_FILE";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "Invalid level '{}', expected error, warn, info, debug or trace",
+                string
+            )),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+/// Modules to trace along with the most detailed level to trace them at
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Filter {
+    directives: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// Whether events of the given level in the module with the given path are traced. A
+    /// directive applies to modules with its name anywhere in their path, or to all modules if
+    /// it is named `all`.
+    pub fn enables(&self, module: &str, level: Level) -> bool {
+        self.directives.iter().any(|(name, most)| {
+            level <= *most && (name == "all" || module.split("::").any(|part| part == name))
+        })
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    /// Parses comma separated `module` or `module=level` directives, where a module without a
+    /// level is traced at every level
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let directives = string
+            .split(',')
+            .map(|directive| match directive.split_once('=') {
+                Some((name, level)) if !name.is_empty() => Ok((name.to_string(), level.parse()?)),
+                None if !directive.is_empty() => Ok((directive.to_string(), Level::Trace)),
+                _ => Err(format!(
+                    "Invalid trace directive '{}', expected module or module=level",
+                    directive
+                )),
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { directives })
+    }
+}
+
+/// Tracing requested on the command line, which takes precedence over the environment
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Options {
+    pub filter: Option<Filter>,
+    /// Path of a file to write the events to instead of stderr
+    pub file: Option<String>,
+}
+
+struct Tracer {
+    filter: Filter,
+    output: Mutex<Box<dyn Write + Send>>,
+}
+
+static TRACER: OnceLock<Option<Tracer>> = OnceLock::new();
+
+fn tracer(options: &Options) -> Result<Option<Tracer>, String> {
+    let filter = match &options.filter {
+        Some(filter) => filter.clone(),
+        None => match env::var(FILTER_VAR) {
+            Ok(filter) => filter.parse()?,
+            Err(_) => return Ok(None),
+        },
+    };
+    let output: Box<dyn Write + Send> = match options.file.clone().or(env::var(FILE_VAR).ok()) {
+        Some(path) => Box::new(
+            File::create(&path).map_err(|error| format!("Failed to create {}: {}", path, error))?,
+        ),
+        None => Box::new(io::stderr()),
+    };
+
+    Ok(Some(Tracer {
+        filter,
+        output: Mutex::new(output),
+    }))
+}
+
+/// Sets up tracing from the given options and the environment. Events emitted earlier have
+/// already set it up from just the environment, in which case the options are ignored.
+pub fn init(options: &Options) -> Result<(), String> {
+    let mut result = Ok(());
+    TRACER.get_or_init(|| {
+        tracer(options).unwrap_or_else(|error| {
+            result = Err(error);
+            None
+        })
+    });
+
+    result
+}
+
+fn get() -> Option<&'static Tracer> {
+    TRACER
+        .get_or_init(|| {
+            tracer(&Options::default()).unwrap_or_else(|error| {
+                eprintln!("warning: Tracing is off: {}", error);
+                None
+            })
+        })
+        .as_ref()
+}
+
+/// Whether any module is traced
+pub fn active() -> bool {
+    get().is_some()
+}
+
+/// Whether events of the given level in the given module are traced
+pub fn enabled(module: &str, level: Level) -> bool {
+    get().is_some_and(|tracer| tracer.filter.enables(module, level))
+}
+
+/// Formats an event as a single line, naming the module without the crate
+pub fn format(module: &str, level: Level, message: &str, fields: &[(&str, String)]) -> String {
+    let module = module.split_once("::").map_or(module, |(_, module)| module);
+    let mut line = format!("{:<5} {}: {}", level, module, message);
+    for (name, value) in fields {
+        line += &format!(" {}={}", name, value);
+    }
+
+    line + "\n"
+}
+
+/// Writes an event, which should have been checked to be [`enabled`]
+pub fn emit(module: &str, level: Level, message: &str, fields: &[(&str, String)]) {
+    if let Some(tracer) = get() {
+        let line = format(module, level, message, fields);
+        if let Ok(mut output) = tracer.output.lock() {
+            let _ = output.write_all(line.as_bytes());
+        }
+    }
+}
+
+/// Emits an event at the given level from the calling module, only evaluating its fields if the
+/// event is traced
+macro_rules! event {
+    ($level:ident, $message:expr $(, $($fields:tt)*)?) => {
+        if $crate::trace::enabled(module_path!(), $crate::trace::Level::$level) {
+            #[allow(unused_mut)]
+            let mut fields: Vec<(&str, String)> = Vec::new();
+            $($crate::trace::fields!(fields; $($fields)*);)?
+            $crate::trace::emit(module_path!(), $crate::trace::Level::$level, $message, &fields);
+        }
+    };
+}
+
+/// Formats the `name = value` and `name = ?value` fields of an event
+macro_rules! fields {
+    ($fields:ident;) => {};
+    ($fields:ident; $name:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $fields.push((stringify!($name), format!("{:?}", $value)));
+        $($crate::trace::fields!($fields; $($rest)*);)?
+    };
+    ($fields:ident; $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $fields.push((stringify!($name), format!("{}", $value)));
+        $($crate::trace::fields!($fields; $($rest)*);)?
+    };
+}
+
+pub(crate) use {event, fields};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Direction, Vec2};
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "day15,day6=debug".parse().unwrap();
+
+        assert!(filter.enables("aoc24::days::day15", Level::Trace));
+        assert!(filter.enables("aoc24::days::day6", Level::Debug));
+        assert!(!filter.enables("aoc24::days::day6", Level::Trace));
+        assert!(!filter.enables("aoc24::days::day16", Level::Error));
+        assert!(!filter.enables("aoc24::search", Level::Error));
+        assert!("all=info"
+            .parse::<Filter>()
+            .unwrap()
+            .enables("aoc24::search", Level::Info));
+
+        assert!("day6=loud".parse::<Filter>().is_err());
+        assert!("day6,,day7".parse::<Filter>().is_err());
+        assert!("=debug".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let mut fields = vec![];
+        fields!(fields; position = Vec2::new(3, 4), direction = ?Direction::East, turns = 2);
+
+        assert_eq!(
+            "DEBUG days::day6: turned position=(3, 4) direction=East turns=2\n",
+            format("aoc24::days::day6", Level::Debug, "turned", &fields)
+        );
+        assert_eq!(
+            "INFO  days::day16: found path\n",
+            format("aoc24::days::day16", Level::Info, "found path", &[])
+        );
+    }
+}
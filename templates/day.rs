@@ -0,0 +1,51 @@
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use std::convert::Infallible;
+
+#[derive(Debug)]
+pub struct Problem {
+    lines: Vec<String>,
+}
+
+impl Problem {
+    fn from_string(string: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            lines: parse::lines(string)
+                .map(|line| line.text.to_string())
+                .collect(),
+        })
+    }
+}
+
+impl Solution for Problem {
+    const DAY: u8 = {day};
+    const SAMPLE: &'static str = include_str!("../../samples/day{day}.txt");
+
+    type Part1 = usize;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::from_string(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn part_2(&self) -> Option<Infallible> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(
+            Problem::SAMPLE.lines().count(),
+            Problem::from_string(Problem::SAMPLE).unwrap().part_1()
+        );
+    }
+}